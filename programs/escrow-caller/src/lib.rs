@@ -0,0 +1,93 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    entrypoint,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{Pubkey, find_program_address},
+};
+use pinocchio_escrow::{
+    MAX_BASKET_ENTRIES, MakeInstructionData,
+    cpi::{MakeCpiAccounts, make_cpi_with_data},
+    read_bytes,
+};
+
+entrypoint!(process_instruction);
+
+// 33333333333333333333333333333333333333333333
+pub const ID: Pubkey = [
+    0x1e, 0x3c, 0xd6, 0x28, 0x43, 0x80, 0x94, 0x0e, 0x08, 0x62, 0x4c, 0xb8, 0x33, 0x8b, 0x77, 0xdc,
+    0x33, 0x25, 0x75, 0xd1, 0x5f, 0xa3, 0x9a, 0x0f, 0x1d, 0xf1, 0x5e, 0xe0, 0x8f, 0xb8, 0x23, 0xee,
+];
+
+// Seed of the PDA this program opens escrows for
+pub const AUTHORITY_SEED: &[u8] = b"authority";
+
+// Opens an escrow with this program's PDA as the maker, funded by the signing payer. The data is
+// the seed, receive, amount and expiry, and the escrow program account goes last
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [
+        authority,
+        escrow,
+        mint_a,
+        mint_b,
+        authority_ata_a,
+        vault,
+        system_program,
+        token_program,
+        associated_token_account_program,
+        config,
+        payer,
+        _escrow_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if instruction_data.len() != 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (authority_key, bump) = find_program_address(&[AUTHORITY_SEED], program_id);
+    if &authority_key != authority.key() {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let bump_binding = [bump];
+    let authority_seeds = [Seed::from(AUTHORITY_SEED), Seed::from(&bump_binding)];
+
+    make_cpi_with_data(
+        &MakeCpiAccounts {
+            maker: authority,
+            escrow,
+            mint_a,
+            mint_b,
+            maker_ata_a: authority_ata_a,
+            vault,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+            payer,
+        },
+        &[Signer::from(&authority_seeds)],
+        &MakeInstructionData {
+            seed: u64::from_le_bytes(read_bytes(instruction_data, 0)?),
+            receive: u64::from_le_bytes(read_bytes(instruction_data, 8)?),
+            amount: u64::from_le_bytes(read_bytes(instruction_data, 16)?),
+            expiry: i64::from_le_bytes(read_bytes(instruction_data, 24)?),
+            start_time: 0,
+            vesting_start: 0,
+            vesting_duration: 0,
+            authorized_taker: [0u8; 32],
+            vault_rent_recipient: [0u8; 32],
+            memo: [0u8; 32],
+            basket_amounts: [0u64; MAX_BASKET_ENTRIES],
+            basket_len: 0,
+        },
+    )
+}
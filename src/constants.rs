@@ -0,0 +1,80 @@
+use core::mem::offset_of;
+use pinocchio::pubkey::Pubkey;
+
+use crate::{
+    Commitment, Config, Escrow, INTEREST_BEARING_CONFIG_EXTENSION, METADATA_POINTER_EXTENSION,
+    MINT_CLOSE_AUTHORITY_EXTENSION, TOKEN_METADATA_EXTENSION, TRANSFER_FEE_CONFIG_EXTENSION,
+};
+
+// Prefix of the escrow PDA seeds, change it to deploy into a separate namespace
+pub const ESCROW_SEED_PREFIX: &[u8] = b"escrow";
+
+// Seed of the singleton config PDA holding the admin and pause switch
+pub const CONFIG_SEED: &[u8] = b"config";
+
+// Prefix of the per-escrow commitment PDA seeds used by CommitTake and RevealTake
+pub const COMMITMENT_SEED: &[u8] = b"commitment";
+
+// Extra deposit mints a basket escrow can offer besides mint A
+pub const MAX_BASKET_ENTRIES: usize = 2;
+
+// Extra mints a maker can accept as payment besides mint B
+pub const MAX_RECEIVE_OPTIONS: usize = 2;
+
+// Smallest amount of token A InitConfig lets Make accept so dust escrows don't bloat state, the
+// admin can raise it with SetMinDeposit for mints with more decimals
+pub const MIN_DEPOSIT_AMOUNT: u64 = 100;
+
+// Basis points denominator, 10_000 bps is 100%
+pub const MAX_BPS: u16 = 10_000;
+
+// Protocol fee InitConfig starts with, the admin can change it with SetFee
+pub const FEE_BPS: u16 = 0;
+
+// Highest protocol fee SetFee accepts, 1% of the receive amount
+pub const MAX_FEE_BPS: u16 = 100;
+
+// Seconds after expiry before anyone can Reap an escrow back to its maker
+pub const REAP_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+// Share of the reclaimed rent paid to whoever Reaps an escrow
+pub const REAP_BOUNTY_BPS: u16 = 1_000;
+
+// Slots a commitment has to wait before RevealTake, so the reveal can't share a block with it
+pub const MIN_REVEAL_DELAY_SLOTS: u64 = 2;
+
+// Slots after which an unrevealed commitment can be replaced, so one can't lock an escrow
+pub const COMMITMENT_EXPIRY_SLOTS: u64 = 150;
+
+// Account sizes for clients pre-computing rent without touching the state types
+pub const ESCROW_ACCOUNT_LEN: usize = Escrow::LEN;
+pub const CONFIG_ACCOUNT_LEN: usize = Config::LEN;
+pub const COMMITMENT_ACCOUNT_LEN: usize = Commitment::LEN;
+
+// Size of a vault under the Token program, Token-2022 vaults grow with the mint's extensions
+pub const VAULT_ACCOUNT_LEN: usize = 165;
+
+pub const fn escrow_space() -> usize {
+    ESCROW_ACCOUNT_LEN
+}
+
+// Byte offset of the current maker in escrow account data, for getProgramAccounts filters
+pub const ESCROW_MAKER_OFFSET: usize = offset_of!(Escrow, maker);
+
+// Fee recipient InitConfig starts with, the admin can change it with SetFeeRecipient
+// DHJ1vHviK8GiUmbCP944oQYdQtiTsEeNkcCu765TmPjg
+pub const TREASURY: Pubkey = [
+    0xb6, 0x78, 0xc6, 0xa3, 0xae, 0xaf, 0xb9, 0x12, 0x40, 0xed, 0x5f, 0x71, 0xbb, 0x93, 0x77, 0x60,
+    0xbe, 0x70, 0x15, 0x20, 0x7a, 0x03, 0x27, 0xbb, 0xe6, 0xd6, 0xc4, 0xf8, 0x05, 0x95, 0x18, 0x03,
+];
+
+// Token-2022 mint extensions accepted for the deposited mint, anything that can move or
+// freeze vault tokens out from under the escrow is left out, including a default account
+// state that would create the vault frozen
+pub const ALLOWED_MINT_EXTENSIONS: &[u16] = &[
+    TRANSFER_FEE_CONFIG_EXTENSION,
+    MINT_CLOSE_AUTHORITY_EXTENSION,
+    INTEREST_BEARING_CONFIG_EXTENSION,
+    METADATA_POINTER_EXTENSION,
+    TOKEN_METADATA_EXTENSION,
+];
@@ -0,0 +1,283 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+};
+
+use crate::{MAX_BASKET_ENTRIES, Make, MakeInstructionData};
+
+// Accounts a calling program passes through to Make, in Make's own order with the payer last
+pub struct MakeCpiAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub maker_ata_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_account_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    // Funds the escrow and vault, a PDA maker holding data can't, pass the maker to pay itself
+    pub payer: &'a AccountInfo,
+}
+
+pub const MAKE_CPI_DATA_LEN: usize = 2 + MakeInstructionData::LEN;
+
+// Version 2 Make data. Baskets aren't supported, their accounts would follow the payer
+pub fn make_cpi_data(
+    instruction_data: &MakeInstructionData,
+) -> Result<[u8; MAKE_CPI_DATA_LEN], ProgramError> {
+    if instruction_data.basket_len != 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut data = [0u8; MAKE_CPI_DATA_LEN];
+    data[0] = *Make::DISCRIMINATOR;
+    data[1] = MakeInstructionData::VERSION_2;
+    data[2..10].copy_from_slice(&instruction_data.seed.to_le_bytes());
+    data[10..18].copy_from_slice(&instruction_data.receive.to_le_bytes());
+    data[18..26].copy_from_slice(&instruction_data.amount.to_le_bytes());
+    data[26..34].copy_from_slice(&instruction_data.expiry.to_le_bytes());
+    data[34..42].copy_from_slice(&instruction_data.start_time.to_le_bytes());
+    data[42..50].copy_from_slice(&instruction_data.vesting_start.to_le_bytes());
+    data[50..58].copy_from_slice(&instruction_data.vesting_duration.to_le_bytes());
+    data[58..90].copy_from_slice(&instruction_data.authorized_taker);
+    data[90..122].copy_from_slice(&instruction_data.vault_rent_recipient);
+    data[122..154].copy_from_slice(&instruction_data.memo);
+    Ok(data)
+}
+
+// Opens an escrow from another program, signer_seeds sign for a PDA maker and leave every option
+// but the seed, receive and amount at its default
+pub fn make_cpi(
+    accounts: &MakeCpiAccounts,
+    signer_seeds: &[Signer],
+    seed: u64,
+    receive: u64,
+    amount: u64,
+) -> ProgramResult {
+    make_cpi_with_data(
+        accounts,
+        signer_seeds,
+        &MakeInstructionData {
+            seed,
+            receive,
+            amount,
+            expiry: 0,
+            start_time: 0,
+            vesting_start: 0,
+            vesting_duration: 0,
+            authorized_taker: [0u8; 32],
+            vault_rent_recipient: [0u8; 32],
+            memo: [0u8; 32],
+            basket_amounts: [0u64; MAX_BASKET_ENTRIES],
+            basket_len: 0,
+        },
+    )
+}
+
+// Same as make_cpi, with the expiry, vesting and every other Make option set by the caller
+pub fn make_cpi_with_data(
+    accounts: &MakeCpiAccounts,
+    signer_seeds: &[Signer],
+    instruction_data: &MakeInstructionData,
+) -> ProgramResult {
+    let account_metas = [
+        AccountMeta::writable_signer(accounts.maker.key()),
+        AccountMeta::writable(accounts.escrow.key()),
+        AccountMeta::readonly(accounts.mint_a.key()),
+        AccountMeta::readonly(accounts.mint_b.key()),
+        AccountMeta::writable(accounts.maker_ata_a.key()),
+        AccountMeta::writable(accounts.vault.key()),
+        AccountMeta::readonly(accounts.system_program.key()),
+        AccountMeta::readonly(accounts.token_program.key()),
+        AccountMeta::readonly(accounts.associated_token_account_program.key()),
+        AccountMeta::readonly(accounts.config.key()),
+        AccountMeta::writable_signer(accounts.payer.key()),
+    ];
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: &account_metas,
+        data: &make_cpi_data(instruction_data)?,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.maker,
+            accounts.escrow,
+            accounts.mint_a,
+            accounts.mint_b,
+            accounts.maker_ata_a,
+            accounts.vault,
+            accounts.system_program,
+            accounts.token_program,
+            accounts.associated_token_account_program,
+            accounts.config,
+            accounts.payer,
+        ],
+        signer_seeds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Escrow, MAX_BASKET_ENTRIES, Make, MakeInstructionData,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, CALLER_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID,
+                SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            },
+            pda::{get_caller_authority_pda, get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    use super::make_cpi_data;
+
+    fn instruction_data(basket_len: usize) -> MakeInstructionData {
+        MakeInstructionData {
+            seed: 42,
+            receive: 100_000_000,
+            amount: 500_000_000,
+            expiry: 1_000,
+            start_time: 10,
+            vesting_start: 20,
+            vesting_duration: 30,
+            authorized_taker: [1; 32],
+            vault_rent_recipient: [2; 32],
+            memo: [3; 32],
+            basket_amounts: [0u64; MAX_BASKET_ENTRIES],
+            basket_len,
+        }
+    }
+
+    #[test]
+    fn make_cpi_data_parses_as_make() {
+        let data = make_cpi_data(&instruction_data(0)).unwrap();
+
+        assert_eq!(data[0], *Make::DISCRIMINATOR);
+
+        let make_data = MakeInstructionData::try_from(&data[1..]).unwrap();
+
+        assert_eq!(make_data.seed, 42);
+        assert_eq!(make_data.receive, 100_000_000);
+        assert_eq!(make_data.amount, 500_000_000);
+        assert_eq!(make_data.expiry, 1_000);
+        assert_eq!(make_data.start_time, 10);
+        assert_eq!(make_data.vesting_start, 20);
+        assert_eq!(make_data.vesting_duration, 30);
+        assert_eq!(make_data.authorized_taker, [1; 32]);
+        assert_eq!(make_data.vault_rent_recipient, [2; 32]);
+        assert_eq!(make_data.memo, [3; 32]);
+        assert_eq!(make_data.basket_len, 0);
+
+        assert_eq!(
+            make_cpi_data(&instruction_data(1)).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn make_cpi() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        litesvm
+            .add_program_from_file(CALLER_PROGRAM_ID, "target/deploy/escrow_caller.so")
+            .unwrap();
+
+        let payer = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+
+        // The caller program's PDA is the maker, and holds no lamports of its own
+        let authority = get_caller_authority_pda();
+        let authority_ata_a = init_ata(litesvm, mint_a, authority, 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 1_000_000;
+        let escrow_pda = get_escrow_pda(&authority, seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: CALLER_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(authority, false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(authority_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(PROGRAM_ID, false),
+            ],
+            data: [
+                seed.to_le_bytes(),
+                receive_amount.to_le_bytes(),
+                give_amount.to_le_bytes(),
+                expiry.to_le_bytes(),
+            ]
+            .concat(),
+        };
+
+        // Only the caller program can sign for its PDA, so Make rejects it when called directly
+        simulate_expect_error(
+            litesvm,
+            &[&payer],
+            &payer.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: ix.accounts[..11].to_vec(),
+                data: make_cpi_data(&instruction_data(0)).unwrap().to_vec(),
+            }],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        let result = build_and_send_transaction(litesvm, &[&payer], &payer.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // The payer funded the escrow and vault, so the PDA maker still holds nothing
+        assert_eq!(
+            litesvm
+                .get_account(&authority)
+                .map_or(0, |account| account.lamports),
+            0
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.maker, authority.to_bytes());
+        assert_eq!(escrow.receive, receive_amount);
+        assert_eq!(escrow.expiry, expiry);
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &authority_ata_a).amount,
+            1_000_000_000 - give_amount
+        );
+    }
+}
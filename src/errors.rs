@@ -10,3 +10,43 @@ pub enum PinocchioError {
     NotSigner,
     InvalidAddress,
 }
+
+impl From<EscrowError> for ProgramError {
+    fn from(e: EscrowError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+// Codes continue after PinocchioError so the two never overlap
+pub enum EscrowError {
+    InvalidEscrowPda = 2,
+    MakerMismatch,
+    MintMismatch,
+    ZeroAmount,
+    Expired,
+    UnauthorizedTaker,
+    SlippageExceeded,
+    VaultRentRecipientMismatch,
+    InvalidTreasury,
+    UnsupportedMintExtension,
+    SameMint,
+    NotStarted,
+    VestingMismatch,
+    EmptyVault,
+    BasketMismatch,
+    InvalidDelegate,
+    InvalidConfig,
+    Paused,
+    ReceiveAssetMismatch,
+    NotReapable,
+    TooManyAccountKeys,
+    VaultFrozen,
+    InvalidMaker,
+    VaultAmountMismatch,
+    VaultNotClosed,
+    DepositTooSmall,
+    CommitmentMismatch,
+    RevealTooEarly,
+    ExpiryNotExtended,
+    SameTokenAccount,
+}
@@ -0,0 +1,438 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{
+    Amend, AmendInstructionData, Claim, CloseEscrow, CommitTake, CommitTakeInstructionData,
+    ExtendExpiry, ExtendExpiryInstructionData, InitConfig, Make, MakeAndTake, MakeDelegated,
+    MakeInstructionData, MigrateEscrow, QueryEscrow, Reap, Refund, RevealTake,
+    RevealTakeInstructionData, SetFee, SetFeeInstructionData, SetFeeRecipient,
+    SetFeeRecipientInstructionData, SetMinDeposit, SetMinDepositInstructionData, SetPaused,
+    SetPausedInstructionData, SetReceiveOptions, SetReceiveOptionsInstructionData, Split,
+    SplitInstructionData, Take, TakeInstructionData, TakeMany, TakeManyInstructionData, TakeNative,
+    TakeTo, TopUp, TopUpInstructionData, TransferMaker, TransferMakerInstructionData,
+    UpdateReceive, UpdateReceiveInstructionData,
+};
+
+// Instruction data of every instruction, routed on the leading discriminator byte
+pub enum Instruction {
+    Make(MakeInstructionData),
+    Take(TakeInstructionData),
+    Refund,
+    TopUp(TopUpInstructionData),
+    UpdateReceive(UpdateReceiveInstructionData),
+    TakeMany(TakeManyInstructionData),
+    QueryEscrow,
+    Amend(AmendInstructionData),
+    Claim,
+    TransferMaker(TransferMakerInstructionData),
+    TakeTo(TakeInstructionData),
+    Split(SplitInstructionData),
+    MakeDelegated(MakeInstructionData),
+    InitConfig,
+    SetPaused(SetPausedInstructionData),
+    TakeNative(TakeInstructionData),
+    Reap,
+    CloseEscrow,
+    SetReceiveOptions(SetReceiveOptionsInstructionData),
+    MigrateEscrow,
+    CommitTake(CommitTakeInstructionData),
+    RevealTake(RevealTakeInstructionData),
+    ExtendExpiry(ExtendExpiryInstructionData),
+    SetFee(SetFeeInstructionData),
+    SetFeeRecipient(SetFeeRecipientInstructionData),
+    MakeAndTake(MakeInstructionData),
+    SetMinDeposit(SetMinDepositInstructionData),
+}
+
+impl Instruction {
+    // Instructions without a payload ignore anything after the discriminator, as on-chain
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let (discriminator, data) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match discriminator {
+            Make::DISCRIMINATOR => Self::Make(MakeInstructionData::try_from(data)?),
+            Take::DISCRIMINATOR => Self::Take(TakeInstructionData::try_from(data)?),
+            Refund::DISCRIMINATOR => Self::Refund,
+            TopUp::DISCRIMINATOR => Self::TopUp(TopUpInstructionData::try_from(data)?),
+            UpdateReceive::DISCRIMINATOR => {
+                Self::UpdateReceive(UpdateReceiveInstructionData::try_from(data)?)
+            }
+            TakeMany::DISCRIMINATOR => Self::TakeMany(TakeManyInstructionData::try_from(data)?),
+            QueryEscrow::DISCRIMINATOR => Self::QueryEscrow,
+            Amend::DISCRIMINATOR => Self::Amend(AmendInstructionData::try_from(data)?),
+            Claim::DISCRIMINATOR => Self::Claim,
+            TransferMaker::DISCRIMINATOR => {
+                Self::TransferMaker(TransferMakerInstructionData::try_from(data)?)
+            }
+            TakeTo::DISCRIMINATOR => Self::TakeTo(TakeInstructionData::try_from(data)?),
+            Split::DISCRIMINATOR => Self::Split(SplitInstructionData::try_from(data)?),
+            MakeDelegated::DISCRIMINATOR => {
+                Self::MakeDelegated(MakeInstructionData::try_from(data)?)
+            }
+            InitConfig::DISCRIMINATOR => Self::InitConfig,
+            SetPaused::DISCRIMINATOR => Self::SetPaused(SetPausedInstructionData::try_from(data)?),
+            TakeNative::DISCRIMINATOR => Self::TakeNative(TakeInstructionData::try_from(data)?),
+            Reap::DISCRIMINATOR => Self::Reap,
+            CloseEscrow::DISCRIMINATOR => Self::CloseEscrow,
+            SetReceiveOptions::DISCRIMINATOR => {
+                Self::SetReceiveOptions(SetReceiveOptionsInstructionData::try_from(data)?)
+            }
+            MigrateEscrow::DISCRIMINATOR => Self::MigrateEscrow,
+            CommitTake::DISCRIMINATOR => {
+                Self::CommitTake(CommitTakeInstructionData::try_from(data)?)
+            }
+            RevealTake::DISCRIMINATOR => {
+                Self::RevealTake(RevealTakeInstructionData::try_from(data)?)
+            }
+            ExtendExpiry::DISCRIMINATOR => {
+                Self::ExtendExpiry(ExtendExpiryInstructionData::try_from(data)?)
+            }
+            SetFee::DISCRIMINATOR => Self::SetFee(SetFeeInstructionData::try_from(data)?),
+            SetFeeRecipient::DISCRIMINATOR => {
+                Self::SetFeeRecipient(SetFeeRecipientInstructionData::try_from(data)?)
+            }
+            MakeAndTake::DISCRIMINATOR => Self::MakeAndTake(MakeInstructionData::try_from(data)?),
+            SetMinDeposit::DISCRIMINATOR => {
+                Self::SetMinDeposit(SetMinDepositInstructionData::try_from(data)?)
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            Self::Make(data) => [vec![*Make::DISCRIMINATOR], pack_make(data)].concat(),
+            Self::Take(data) => [vec![*Take::DISCRIMINATOR], pack_take(data)].concat(),
+            Self::Refund => vec![*Refund::DISCRIMINATOR],
+            Self::TopUp(data) => [
+                vec![*TopUp::DISCRIMINATOR],
+                data.amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::UpdateReceive(data) => [
+                vec![*UpdateReceive::DISCRIMINATOR],
+                data.receive.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::TakeMany(data) => vec![*TakeMany::DISCRIMINATOR, data.count],
+            Self::QueryEscrow => vec![*QueryEscrow::DISCRIMINATOR],
+            Self::Amend(data) => [
+                vec![*Amend::DISCRIMINATOR],
+                data.amount.to_le_bytes().to_vec(),
+                data.receive.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::Claim => vec![*Claim::DISCRIMINATOR],
+            Self::TransferMaker(data) => {
+                [vec![*TransferMaker::DISCRIMINATOR], data.new_maker.to_vec()].concat()
+            }
+            Self::TakeTo(data) => [vec![*TakeTo::DISCRIMINATOR], pack_take(data)].concat(),
+            Self::Split(data) => [
+                vec![*Split::DISCRIMINATOR],
+                data.seed.to_le_bytes().to_vec(),
+                data.amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::MakeDelegated(data) => {
+                [vec![*MakeDelegated::DISCRIMINATOR], pack_make(data)].concat()
+            }
+            Self::InitConfig => vec![*InitConfig::DISCRIMINATOR],
+            Self::SetPaused(data) => vec![*SetPaused::DISCRIMINATOR, data.paused as u8],
+            Self::TakeNative(data) => [vec![*TakeNative::DISCRIMINATOR], pack_take(data)].concat(),
+            Self::Reap => vec![*Reap::DISCRIMINATOR],
+            Self::CloseEscrow => vec![*CloseEscrow::DISCRIMINATOR],
+            Self::SetReceiveOptions(data) => [
+                vec![*SetReceiveOptions::DISCRIMINATOR],
+                data.options[..data.len]
+                    .iter()
+                    .flat_map(|option| {
+                        [option.mint.as_slice(), &option.receive.to_le_bytes()].concat()
+                    })
+                    .collect(),
+            ]
+            .concat(),
+            Self::MigrateEscrow => vec![*MigrateEscrow::DISCRIMINATOR],
+            Self::CommitTake(data) => {
+                [vec![*CommitTake::DISCRIMINATOR], data.hash.to_vec()].concat()
+            }
+            Self::RevealTake(data) => [
+                vec![*RevealTake::DISCRIMINATOR],
+                data.nonce.to_vec(),
+                pack_take(&data.take),
+            ]
+            .concat(),
+            Self::ExtendExpiry(data) => [
+                vec![*ExtendExpiry::DISCRIMINATOR],
+                data.expiry.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::SetFee(data) => [
+                vec![*SetFee::DISCRIMINATOR],
+                data.fee_bps.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+            Self::SetFeeRecipient(data) => [
+                vec![*SetFeeRecipient::DISCRIMINATOR],
+                data.fee_recipient.to_vec(),
+            ]
+            .concat(),
+            Self::MakeAndTake(data) => {
+                [vec![*MakeAndTake::DISCRIMINATOR], pack_make(data)].concat()
+            }
+            Self::SetMinDeposit(data) => [
+                vec![*SetMinDeposit::DISCRIMINATOR],
+                data.min_deposit.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        }
+    }
+}
+
+// Packs the latest layout
+fn pack_make(data: &MakeInstructionData) -> Vec<u8> {
+    [
+        vec![MakeInstructionData::VERSION_2],
+        data.seed.to_le_bytes().to_vec(),
+        data.receive.to_le_bytes().to_vec(),
+        data.amount.to_le_bytes().to_vec(),
+        data.expiry.to_le_bytes().to_vec(),
+        data.start_time.to_le_bytes().to_vec(),
+        data.vesting_start.to_le_bytes().to_vec(),
+        data.vesting_duration.to_le_bytes().to_vec(),
+        data.authorized_taker.to_vec(),
+        data.vault_rent_recipient.to_vec(),
+        data.memo.to_vec(),
+        data.basket_amounts[..data.basket_len]
+            .iter()
+            .flat_map(|amount| amount.to_le_bytes())
+            .collect(),
+    ]
+    .concat()
+}
+
+// Packs the shortest payload that carries every field set
+fn pack_take(data: &TakeInstructionData) -> Vec<u8> {
+    match (
+        data.fill_amount,
+        data.max_receive,
+        data.expected_vault_amount,
+    ) {
+        (None, 0, 0) => vec![],
+        (Some(fill_amount), 0, 0) => fill_amount.to_le_bytes().to_vec(),
+        (fill_amount, max_receive, 0) => [
+            fill_amount.unwrap_or_default().to_le_bytes().to_vec(),
+            max_receive.to_le_bytes().to_vec(),
+        ]
+        .concat(),
+        (fill_amount, max_receive, expected_vault_amount) => [
+            fill_amount.unwrap_or_default().to_le_bytes().to_vec(),
+            max_receive.to_le_bytes().to_vec(),
+            expected_vault_amount.to_le_bytes().to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+
+    use crate::{
+        AmendInstructionData, CommitTakeInstructionData, ExtendExpiryInstructionData, Instruction,
+        MAX_BASKET_ENTRIES, MakeInstructionData, ReceiveOption, ReceiveOptions,
+        RevealTakeInstructionData, SetFeeInstructionData, SetFeeRecipientInstructionData,
+        SetMinDepositInstructionData, SetPausedInstructionData, SetReceiveOptionsInstructionData,
+        SplitInstructionData, TakeInstructionData, TakeManyInstructionData, TopUpInstructionData,
+        TransferMakerInstructionData, UpdateReceiveInstructionData,
+    };
+
+    fn make_data(basket_len: usize) -> MakeInstructionData {
+        MakeInstructionData {
+            seed: 42,
+            receive: 100_000_000,
+            amount: 500_000_000,
+            expiry: 1_000,
+            start_time: 0,
+            vesting_start: 0,
+            vesting_duration: 0,
+            authorized_taker: [4; 32],
+            vault_rent_recipient: [5; 32],
+            memo: [6; 32],
+            basket_amounts: [250_000_000; MAX_BASKET_ENTRIES],
+            basket_len,
+        }
+    }
+
+    fn take_data(
+        fill_amount: Option<u64>,
+        max_receive: u64,
+        expected_vault_amount: u64,
+    ) -> TakeInstructionData {
+        TakeInstructionData {
+            fill_amount,
+            max_receive,
+            expected_vault_amount,
+        }
+    }
+
+    #[test]
+    fn instruction_pack_and_unpack_every_variant() {
+        let mut options = ReceiveOptions::default();
+        options[0] = ReceiveOption {
+            mint: [8; 32],
+            receive: 12,
+        };
+
+        let instructions = [
+            Instruction::Make(make_data(0)),
+            Instruction::Make(make_data(1)),
+            Instruction::Take(take_data(None, 0, 0)),
+            Instruction::Take(take_data(Some(100), 0, 0)),
+            Instruction::Take(take_data(None, 90, 0)),
+            Instruction::Take(take_data(Some(100), 90, 500)),
+            Instruction::Refund,
+            Instruction::TopUp(TopUpInstructionData { amount: 7 }),
+            Instruction::UpdateReceive(UpdateReceiveInstructionData { receive: 8 }),
+            Instruction::TakeMany(TakeManyInstructionData { count: 2 }),
+            Instruction::QueryEscrow,
+            Instruction::Amend(AmendInstructionData {
+                amount: 9,
+                receive: 10,
+            }),
+            Instruction::Claim,
+            Instruction::TransferMaker(TransferMakerInstructionData { new_maker: [7; 32] }),
+            Instruction::TakeTo(take_data(Some(100), 0, 0)),
+            Instruction::Split(SplitInstructionData {
+                seed: 43,
+                amount: 11,
+            }),
+            Instruction::MakeDelegated(make_data(0)),
+            Instruction::InitConfig,
+            Instruction::SetPaused(SetPausedInstructionData { paused: true }),
+            Instruction::TakeNative(take_data(None, 0, 0)),
+            Instruction::Reap,
+            Instruction::CloseEscrow,
+            Instruction::SetReceiveOptions(SetReceiveOptionsInstructionData { options, len: 1 }),
+            Instruction::SetReceiveOptions(SetReceiveOptionsInstructionData {
+                options: ReceiveOptions::default(),
+                len: 0,
+            }),
+            Instruction::MigrateEscrow,
+            Instruction::CommitTake(CommitTakeInstructionData { hash: [9; 32] }),
+            Instruction::RevealTake(RevealTakeInstructionData {
+                nonce: [10; 32],
+                take: take_data(Some(100), 0, 0),
+            }),
+            Instruction::ExtendExpiry(ExtendExpiryInstructionData { expiry: 2_000 }),
+            Instruction::SetFee(SetFeeInstructionData { fee_bps: 50 }),
+            Instruction::SetFeeRecipient(SetFeeRecipientInstructionData {
+                fee_recipient: [11; 32],
+            }),
+            Instruction::MakeAndTake(make_data(0)),
+            Instruction::SetMinDeposit(SetMinDepositInstructionData { min_deposit: 1_000 }),
+        ];
+
+        // Every discriminator is covered, and each packed payload unpacks to the same instruction
+        let mut discriminators = [false; 27];
+        for instruction in instructions {
+            let packed = instruction.pack();
+            discriminators[packed[0] as usize] = true;
+
+            assert_eq!(Instruction::unpack(&packed).unwrap().pack(), packed);
+        }
+
+        assert!(discriminators.iter().all(|&covered| covered));
+    }
+
+    #[test]
+    fn instruction_pack_make() {
+        let packed = Instruction::Make(make_data(1)).pack();
+
+        assert_eq!(packed.len(), 2 + MakeInstructionData::LEN + 8);
+        assert_eq!(packed[..2], [0, MakeInstructionData::VERSION_2]);
+
+        let Instruction::Make(data) = Instruction::unpack(&packed).unwrap() else {
+            panic!("expected Make");
+        };
+
+        assert_eq!(data.seed, 42);
+        assert_eq!(data.receive, 100_000_000);
+        assert_eq!(data.amount, 500_000_000);
+        assert_eq!(data.expiry, 1_000);
+        assert_eq!(data.authorized_taker, [4; 32]);
+        assert_eq!(data.vault_rent_recipient, [5; 32]);
+        assert_eq!(data.memo, [6; 32]);
+        assert_eq!(data.basket_len, 1);
+        assert_eq!(data.basket_amounts[0], 250_000_000);
+    }
+
+    #[test]
+    fn instruction_pack_take() {
+        // The shortest payload carrying every set field is packed
+        assert_eq!(Instruction::Take(take_data(None, 0, 0)).pack(), [1]);
+        assert_eq!(
+            Instruction::Take(take_data(Some(100), 0, 0)).pack().len(),
+            1 + 8
+        );
+        assert_eq!(
+            Instruction::Take(take_data(None, 90, 0)).pack().len(),
+            1 + 16
+        );
+        assert_eq!(
+            Instruction::Take(take_data(None, 0, 500)).pack().len(),
+            1 + 24
+        );
+
+        let Instruction::Take(data) =
+            Instruction::unpack(&Instruction::Take(take_data(Some(100), 90, 500)).pack()).unwrap()
+        else {
+            panic!("expected Take");
+        };
+
+        assert_eq!(data.fill_amount, Some(100));
+        assert_eq!(data.max_receive, 90);
+        assert_eq!(data.expected_vault_amount, 500);
+    }
+
+    #[test]
+    fn instruction_unpack_rejects_unknown_discriminators() {
+        for data in [vec![], vec![27u8], vec![u8::MAX]] {
+            assert_eq!(
+                Instruction::unpack(&data).err(),
+                Some(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn instruction_unpack_never_panics() {
+        // xorshift64, so the inputs are random but the same on every run
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        // Every discriminator with payloads of every length up to the largest Make, plus a few
+        // Make payloads that start with a valid version byte so the later checks are reached
+        let max_len = 2 + MakeInstructionData::LEN + 8 * MAX_BASKET_ENTRIES + 8;
+        for _ in 0..20_000 {
+            let len = (next() % max_len as u64) as usize;
+            let mut data: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+
+            if let Some(discriminator) = data.first_mut() {
+                *discriminator %= 28;
+            }
+            if data.len() > 1 && next() % 2 == 0 {
+                data[0] = 0;
+                data[1] = (next() % 4) as u8;
+            }
+
+            // Reaching the end of the call means the parser returned instead of panicking
+            let _ = Instruction::unpack(&data);
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+};
+use pinocchio_token_2022::instructions::TransferChecked;
+
+use crate::{
+    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, Config, ConfigAccount,
+    ESCROW_SEED_PREFIX, Escrow, EscrowError, MAX_RECEIVE_OPTIONS, MintInterface, ProgramAccount,
+    SignerAccount, TokenAccountInterface, TokenProgramInterface, assert_is_maker, read_bytes,
+};
+
+pub struct AmendAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub maker_ata_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AmendAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            config,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        TokenProgramInterface::check(token_program)?;
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            maker_ata_a,
+            vault,
+            token_program,
+            config,
+        })
+    }
+}
+
+pub struct AmendInstructionData {
+    pub amount: u64,
+    pub receive: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for AmendInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(read_bytes(data, 0)?);
+        let receive = u64::from_le_bytes(read_bytes(data, 8)?);
+
+        // Instruction Checks
+        if amount == 0 || receive == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount, receive })
+    }
+}
+
+pub struct Amend<'a> {
+    pub accounts: AmendAccounts<'a>,
+    pub instruction_data: AmendInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Amend<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = AmendAccounts::try_from(accounts)?;
+        let instruction_data = AmendInstructionData::try_from(data)?;
+
+        // Check if the protocol is paused before moving any tokens
+        ConfigAccount::check_not_paused(accounts.config)?;
+
+        // Check if the new deposit meets the minimum, as Make does for a new escrow
+        if instruction_data.amount
+            < Config::load(&accounts.config.try_borrow_data()?)?.min_deposit()
+        {
+            return Err(EscrowError::DepositTooSmall.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Amend<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+
+        // Check if the maker signed for the escrow
+        assert_is_maker(escrow, self.accounts.maker)?;
+
+        // Check if the mint matches the escrow
+        if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+
+        // Vesting escrows are locked until fully claimed
+        if escrow.vesting_duration != 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Reprice the escrow in place so it can't be taken at the old terms in between
+        escrow.set_receive(self.instruction_data.receive);
+
+        // Receive options were priced against the old deposit, so the maker sets them again
+        for index in 0..MAX_RECEIVE_OPTIONS {
+            escrow.set_receive_option(index, [0u8; 32], 0);
+        }
+
+        let creator_binding = escrow.creator;
+        let seed_binding = escrow.seed.to_le_bytes();
+        let bump_binding = escrow.bump;
+        drop(data);
+
+        let escrow_seeds = [
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(&creator_binding),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&escrow_seeds);
+
+        let refund_amount = TokenAccountInterface::amount(self.accounts.vault)?;
+        let decimals = MintInterface::decimals(self.accounts.mint_a)?;
+
+        // Refund the current deposit from the Vault to the Maker
+        TransferChecked {
+            from: self.accounts.vault,
+            mint: self.accounts.mint_a,
+            to: self.accounts.maker_ata_a,
+            authority: self.accounts.escrow,
+            amount: refund_amount,
+            decimals,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(&[signer])?;
+
+        // Deposit the new amount into the Vault
+        TransferChecked {
+            from: self.accounts.maker_ata_a,
+            mint: self.accounts.mint_a,
+            to: self.accounts.vault,
+            authority: self.accounts.maker,
+            amount: self.instruction_data.amount,
+            decimals,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke()?;
+
+        // Log the escrow, refunded, deposited and receive amounts
+        sol_log("Instruction: Amend");
+        pubkey::log(self.accounts.escrow.key());
+        sol_log_64(
+            refund_amount,
+            self.instruction_data.amount,
+            self.instruction_data.receive,
+            0,
+            0,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Escrow, EscrowError, MIN_DEPOSIT_AMOUNT,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn amend() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let new_receive_amount: u64 = 150_000_000;
+        let new_give_amount: u64 = 300_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![7u8],
+                new_give_amount.to_le_bytes().to_vec(),
+                new_receive_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            new_give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            1_000_000_000 - new_give_amount
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.receive, new_receive_amount);
+    }
+
+    #[test]
+    fn amend_with_min_deposit_and_pause() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let new_receive_amount: u64 = 150_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let amend_ix = |new_give_amount: u64| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![7u8],
+                new_give_amount.to_le_bytes().to_vec(),
+                new_receive_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        // The deposit can't be swapped for one below the minimum
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[amend_ix(MIN_DEPOSIT_AMOUNT - 1)],
+            EscrowError::DepositTooSmall.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new_readonly(default_payer.pubkey(), true),
+                    AccountMeta::new(get_config_pda(), false),
+                ],
+                data: vec![14u8, 1],
+            }],
+        );
+
+        assert!(result.is_ok());
+
+        // Amend moves tokens into the vault, so it stops while the protocol is paused
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[amend_ix(MIN_DEPOSIT_AMOUNT)],
+            EscrowError::Paused.into(),
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn amend_clears_receive_options() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_c = init_ata(litesvm, mint_c, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_c = init_ata(litesvm, mint_c, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let receive_c: u64 = 200_000_000;
+        let new_receive_amount: u64 = 150_000_000;
+        let new_give_amount: u64 = 300_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![1u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // The maker also accepts mint C, priced against the first deposit
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+            ],
+            data: [
+                vec![18u8],
+                mint_c.to_bytes().to_vec(),
+                receive_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![7u8],
+                new_give_amount.to_le_bytes().to_vec(),
+                new_receive_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert!(escrow.receive_options().is_empty());
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_c = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_c,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Mint C is no longer accepted at the price set before the Amend
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(taker.pubkey(), true),
+                    AccountMeta::new(maker.pubkey(), false),
+                    AccountMeta::new(escrow_pda, false),
+                    AccountMeta::new_readonly(mint_a, false),
+                    AccountMeta::new_readonly(mint_c, false),
+                    AccountMeta::new(vault, false),
+                    AccountMeta::new(taker_ata_a, false),
+                    AccountMeta::new(taker_ata_c, false),
+                    AccountMeta::new(maker_ata_c, false),
+                    AccountMeta::new(maker.pubkey(), false),
+                    AccountMeta::new(treasury_ata_c, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(get_config_pda(), false),
+                ],
+                data: vec![1u8],
+            }],
+            EscrowError::MintMismatch.into(),
+        );
+    }
+}
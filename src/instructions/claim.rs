@@ -0,0 +1,474 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+    sysvars::{Sysvar, clock::Clock},
+};
+use pinocchio_token_2022::instructions::{CloseAccount, TransferChecked};
+
+use crate::{
+    AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, AssociatedTokenProgram, ESCROW_SEED_PREFIX, Escrow, EscrowError,
+    HarvestWithheldTokensToMint, MintInterface, ProgramAccount, SignerAccount, SystemProgram,
+    TRANSFER_FEE_CONFIG_EXTENSION, TokenAccountInterface, TokenProgramInterface, checked_mul_div,
+};
+
+pub struct ClaimAccounts<'a> {
+    pub beneficiary: &'a AccountInfo,
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub beneficiary_ata_a: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_account_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClaimAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            beneficiary,
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            beneficiary_ata_a,
+            system_program,
+            token_program,
+            associated_token_account_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        SignerAccount::check(beneficiary)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+
+        // Return the accounts
+        Ok(Self {
+            beneficiary,
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            beneficiary_ata_a,
+            system_program,
+            token_program,
+            associated_token_account_program,
+        })
+    }
+}
+
+pub struct Claim<'a> {
+    pub accounts: ClaimAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Claim<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClaimAccounts::try_from(accounts)?;
+
+        AssociatedTokenAccount::init_if_needed(
+            accounts.beneficiary_ata_a,
+            accounts.mint_a,
+            accounts.beneficiary,
+            accounts.beneficiary,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Claim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow_data()?;
+        let escrow = Escrow::load(&data)?;
+
+        // Check if the maker matches the escrow
+        if escrow.maker.ne(self.accounts.maker.key()) {
+            return Err(EscrowError::MakerMismatch.into());
+        }
+
+        // Check if the mint matches the escrow
+        if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+
+        // Only vesting escrows can be claimed
+        if escrow.vesting_duration == 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
+
+        // The authorized taker is the beneficiary, or the maker for a self-vesting lock
+        let beneficiary = if escrow.authorized_taker == [0u8; 32] {
+            &escrow.maker
+        } else {
+            &escrow.authorized_taker
+        };
+        if beneficiary.ne(self.accounts.beneficiary.key()) {
+            return Err(EscrowError::UnauthorizedTaker.into());
+        }
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        let creator_binding = escrow.creator;
+        let seed_binding = escrow.seed.to_le_bytes();
+        let bump_binding = escrow.bump;
+        let escrow_seeds = [
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(&creator_binding),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&escrow_seeds);
+
+        // Vest the deposit linearly over the vesting window
+        let vault_amount = TokenAccountInterface::amount(self.accounts.vault)?;
+        let total = escrow
+            .released
+            .checked_add(vault_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let elapsed = Clock::get()?
+            .unix_timestamp
+            .saturating_sub(escrow.vesting_start)
+            .clamp(0, escrow.vesting_duration);
+        let vested = checked_mul_div(total, elapsed as u64, escrow.vesting_duration as u64)?;
+        let amount = vested
+            .checked_sub(escrow.released)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if amount == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        let released = escrow.released + amount;
+        drop(data);
+
+        // Transfer the vested amount from the Vault to the Beneficiary
+        TransferChecked {
+            from: self.accounts.vault,
+            mint: self.accounts.mint_a,
+            to: self.accounts.beneficiary_ata_a,
+            authority: self.accounts.escrow,
+            amount,
+            decimals: MintInterface::decimals(self.accounts.mint_a)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        if amount == vault_amount {
+            // Harvest fees withheld in the Vault, which would otherwise block closing it
+            if MintInterface::has_extension(self.accounts.mint_a, TRANSFER_FEE_CONFIG_EXTENSION)? {
+                HarvestWithheldTokensToMint {
+                    mint: self.accounts.mint_a,
+                    source: self.accounts.vault,
+                }
+                .invoke()?;
+            }
+
+            // Close the Vault and the Escrow once everything has been claimed
+            CloseAccount {
+                account: self.accounts.vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.escrow,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        } else {
+            let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+            Escrow::load_mut(data.as_mut())?.set_released(released);
+        }
+
+        // Log the escrow, beneficiary, claimed and total released amounts
+        sol_log("Instruction: Claim");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.beneficiary.key());
+        sol_log_64(amount, released, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use solana_clock::Clock;
+    use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use solana_transaction_error::TransactionError;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Escrow, EscrowError, PinocchioError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+            },
+        },
+    };
+
+    const GIVE_AMOUNT: u64 = 500_000_000;
+    const VESTING_DURATION: i64 = 1_000;
+
+    // Makes an escrow vesting to the beneficiary and moves the clock into the window
+    fn make_vesting_escrow(
+        litesvm: &mut LiteSVM,
+        elapsed: i64,
+    ) -> (Keypair, Keypair, Pubkey, Pubkey, Pubkey) {
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let beneficiary = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let vesting_start = clock.unix_timestamp;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                GIVE_AMOUNT.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                vesting_start.to_le_bytes().to_vec(),
+                VESTING_DURATION.to_le_bytes().to_vec(),
+                beneficiary.pubkey().to_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        clock.unix_timestamp = vesting_start + elapsed;
+        litesvm.set_sysvar(&clock);
+
+        (maker, beneficiary, mint_a, escrow_pda, vault)
+    }
+
+    fn claim_ix(
+        beneficiary: &Pubkey,
+        maker: &Pubkey,
+        mint_a: &Pubkey,
+        escrow_pda: &Pubkey,
+        vault: &Pubkey,
+    ) -> Instruction {
+        let beneficiary_ata_a =
+            get_associated_token_address_with_program_id(beneficiary, mint_a, &TOKEN_PROGRAM_ID);
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*beneficiary, true),
+                AccountMeta::new(*maker, false),
+                AccountMeta::new(*escrow_pda, false),
+                AccountMeta::new_readonly(*mint_a, false),
+                AccountMeta::new(*vault, false),
+                AccountMeta::new(beneficiary_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![8u8],
+        }
+    }
+
+    #[test]
+    fn claim_at_vesting_start() {
+        let (litesvm, _default_payer) = &mut setup();
+        let (maker, beneficiary, mint_a, escrow_pda, vault) = make_vesting_escrow(litesvm, 0);
+
+        let ix = claim_ix(
+            &beneficiary.pubkey(),
+            &maker.pubkey(),
+            &mint_a,
+            &escrow_pda,
+            &vault,
+        );
+
+        let result =
+            build_and_send_transaction(litesvm, &[&beneficiary], &beneficiary.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::ZeroAmount as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn claim_halfway_through_vesting() {
+        let (litesvm, _default_payer) = &mut setup();
+        let (maker, beneficiary, mint_a, escrow_pda, vault) =
+            make_vesting_escrow(litesvm, VESTING_DURATION / 2);
+
+        let ix = claim_ix(
+            &beneficiary.pubkey(),
+            &maker.pubkey(),
+            &mint_a,
+            &escrow_pda,
+            &vault,
+        );
+
+        let result =
+            build_and_send_transaction(litesvm, &[&beneficiary], &beneficiary.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let beneficiary_ata_a = get_associated_token_address_with_program_id(
+            &beneficiary.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &beneficiary_ata_a).amount,
+            GIVE_AMOUNT / 2
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            GIVE_AMOUNT / 2
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.released, GIVE_AMOUNT / 2);
+    }
+
+    #[test]
+    fn claim_after_vesting_ends() {
+        let (litesvm, _default_payer) = &mut setup();
+        let (maker, beneficiary, mint_a, escrow_pda, vault) =
+            make_vesting_escrow(litesvm, VESTING_DURATION);
+
+        let ix = claim_ix(
+            &beneficiary.pubkey(),
+            &maker.pubkey(),
+            &mint_a,
+            &escrow_pda,
+            &vault,
+        );
+
+        let result =
+            build_and_send_transaction(litesvm, &[&beneficiary], &beneficiary.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let beneficiary_ata_a = get_associated_token_address_with_program_id(
+            &beneficiary.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &beneficiary_ata_a).amount,
+            GIVE_AMOUNT
+        );
+        assert!(litesvm.get_account(&vault).is_none());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+    }
+
+    #[test]
+    fn claim_with_non_canonical_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+        let (maker, beneficiary, mint_a, escrow_pda, vault) =
+            make_vesting_escrow(litesvm, VESTING_DURATION);
+
+        // Copy the vault to an address that isn't the escrow's ATA, leaving the real one full
+        let fake_vault = Pubkey::new_unique();
+        let vault_acc = litesvm.get_account(&vault).unwrap();
+        litesvm.set_account(fake_vault, vault_acc).unwrap();
+
+        let ix = claim_ix(
+            &beneficiary.pubkey(),
+            &maker.pubkey(),
+            &mint_a,
+            &escrow_pda,
+            &fake_vault,
+        );
+
+        let result =
+            build_and_send_transaction(litesvm, &[&beneficiary], &beneficiary.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(PinocchioError::InvalidAddress as u32)
+            )
+        );
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            GIVE_AMOUNT
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, create_program_address, find_program_address},
+};
+
+use crate::{
+    AccountCheck, AccountClose, ESCROW_SEED_PREFIX, Escrow, EscrowError, PinocchioError,
+    ProgramAccount, SignerAccount, TokenProgramInterface, account_groups, assert_is_maker,
+};
+
+pub struct CloseEscrowAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub basket: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseEscrowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow, mint_a, vault, token_program, basket @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // A mint and vault follow for each basket entry, anything left over is surplus
+        if basket.len() % 2 != 0 {
+            return Err(EscrowError::TooManyAccountKeys.into());
+        }
+
+        // Basic Accounts Checks
+        TokenProgramInterface::check(token_program)?;
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+
+        // Return the accounts
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            token_program,
+            basket,
+        })
+    }
+}
+
+pub struct CloseEscrow<'a> {
+    pub accounts: CloseEscrowAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseEscrow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CloseEscrowAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CloseEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    // Only for escrows left behind once their vaults were closed some other way
+    pub fn process(&self) -> ProgramResult {
+        {
+            let data = self.accounts.escrow.try_borrow_data()?;
+            let escrow = Escrow::load(&data)?;
+
+            // Check if the maker signed for the escrow
+            assert_is_maker(escrow, self.accounts.maker)?;
+
+            // Check if the escrow is valid
+            let escrow_key = create_program_address(
+                &[
+                    ESCROW_SEED_PREFIX,
+                    &escrow.creator,
+                    &escrow.seed.to_le_bytes(),
+                    &escrow.bump,
+                ],
+                &crate::ID,
+            )?;
+            if &escrow_key != self.accounts.escrow.key() {
+                return Err(EscrowError::InvalidEscrowPda.into());
+            }
+
+            // Check if the mint matches the escrow
+            if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+                return Err(EscrowError::MintMismatch.into());
+            }
+
+            // Check if there is a mint and vault for each basket entry
+            let basket = escrow.basket();
+            if self.accounts.basket.len() != basket.len() * 2 {
+                return Err(EscrowError::BasketMismatch.into());
+            }
+
+            // Every vault must be the escrow's ATA and already gone, so no tokens are stranded
+            self.check_vault_closed(self.accounts.mint_a, self.accounts.vault)?;
+
+            for (entry, [mint, vault]) in
+                basket.iter().zip(account_groups::<2>(self.accounts.basket))
+            {
+                if entry.mint.ne(mint.key()) {
+                    return Err(EscrowError::BasketMismatch.into());
+                }
+
+                self.check_vault_closed(mint, vault)?;
+            }
+        }
+
+        // Close the Escrow, returning its rent to the maker
+        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+
+        // Log the escrow
+        sol_log("Instruction: CloseEscrow");
+        pubkey::log(self.accounts.escrow.key());
+
+        Ok(())
+    }
+
+    fn check_vault_closed(&self, mint: &AccountInfo, vault: &AccountInfo) -> ProgramResult {
+        // The vault address depends on the token program, so it must be the one owning the mint
+        if mint.owner().ne(self.accounts.token_program.key()) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if find_program_address(
+            &[
+                self.accounts.escrow.key(),
+                self.accounts.token_program.key(),
+                mint.key(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        )
+        .0
+        .ne(vault.key())
+        {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        if !vault.data_is_empty() {
+            return Err(EscrowError::VaultNotClosed.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_account::Account;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use pinocchio::program_error::ProgramError;
+
+    use crate::{
+        EscrowError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn close_escrow() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![17u8],
+        };
+
+        // The escrow can't be closed while its vault still holds the deposit
+        simulate_expect_error(
+            litesvm,
+            &[default_payer, &maker],
+            &default_payer.pubkey(),
+            core::slice::from_ref(&ix),
+            EscrowError::VaultNotClosed.into(),
+        );
+
+        // The vault is closed out-of-band, leaving the escrow behind
+        litesvm.set_account(vault, Account::default()).unwrap();
+
+        let maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+        let escrow_lamports = litesvm.get_account(&escrow_pda).unwrap().lamports;
+
+        // The default payer covers the transaction fee so the maker only gains the escrow rent
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer, &maker],
+            &default_payer.pubkey(),
+            &[ix],
+        );
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert_eq!(
+            litesvm.get_account(&maker.pubkey()).unwrap().lamports,
+            maker_lamports + escrow_lamports
+        );
+    }
+
+    #[test]
+    fn close_escrow_with_wrong_token_program() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Under the other token program the vault address is empty, but isn't the real vault
+        let wrong_vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        simulate_expect_error(
+            litesvm,
+            &[default_payer, &maker],
+            &default_payer.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(maker.pubkey(), true),
+                    AccountMeta::new(escrow_pda, false),
+                    AccountMeta::new_readonly(mint_a, false),
+                    AccountMeta::new_readonly(wrong_vault, false),
+                    AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                ],
+                data: vec![17u8],
+            }],
+            ProgramError::IncorrectProgramId,
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+    }
+}
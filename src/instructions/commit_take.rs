@@ -0,0 +1,142 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, find_program_address},
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    AccountCheck, COMMITMENT_EXPIRY_SLOTS, COMMITMENT_SEED, Commitment, CommitmentAccount,
+    ProgramAccount, ProgramAccountInit, SignerAccount, SystemProgram, read_bytes,
+};
+
+pub struct CommitTakeAccounts<'a> {
+    pub taker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub commitment: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CommitTakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [taker, escrow, commitment, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        SignerAccount::check(taker)?;
+        ProgramAccount::check(escrow)?;
+
+        // Return the accounts
+        Ok(Self {
+            taker,
+            escrow,
+            commitment,
+            system_program,
+        })
+    }
+}
+
+pub struct CommitTakeInstructionData {
+    pub hash: [u8; 32],
+}
+
+impl<'a> TryFrom<&'a [u8]> for CommitTakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<[u8; 32]>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let hash = read_bytes(data, 0)?;
+
+        Ok(Self { hash })
+    }
+}
+
+pub struct CommitTake<'a> {
+    pub accounts: CommitTakeAccounts<'a>,
+    pub instruction_data: CommitTakeInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CommitTake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CommitTakeAccounts::try_from(accounts)?;
+        let instruction_data = CommitTakeInstructionData::try_from(data)?;
+
+        let (commitment_key, bump) =
+            find_program_address(&[COMMITMENT_SEED, accounts.escrow.key()], &crate::ID);
+        if &commitment_key != accounts.commitment.key() {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> CommitTake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    // Only the hash goes on-chain, so the taker stays hidden until RevealTake
+    pub fn process(&mut self) -> ProgramResult {
+        let slot = Clock::get()?.slot;
+
+        if self.accounts.commitment.data_is_empty() {
+            let bump_binding = [self.bump];
+            let commitment_seeds = [
+                Seed::from(COMMITMENT_SEED),
+                Seed::from(self.accounts.escrow.key()),
+                Seed::from(&bump_binding),
+            ];
+
+            ProgramAccount::init::<Commitment>(
+                self.accounts.taker,
+                self.accounts.commitment,
+                &commitment_seeds,
+                Commitment::LEN,
+            )?;
+
+            let mut data = self.accounts.commitment.try_borrow_mut_data()?;
+            Commitment::load_mut_uninitialized(data.as_mut())?.set_inner(
+                *self.accounts.escrow.key(),
+                self.instruction_data.hash,
+                slot,
+                bump_binding,
+            );
+        } else {
+            CommitmentAccount::check(self.accounts.commitment)?;
+
+            // A pending commitment can only be replaced once it has gone stale
+            let mut data = self.accounts.commitment.try_borrow_mut_data()?;
+            let commitment = Commitment::load_mut(data.as_mut())?;
+
+            if slot < commitment.slot().saturating_add(COMMITMENT_EXPIRY_SLOTS) {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+
+            commitment.set_commitment(self.instruction_data.hash, slot);
+        }
+
+        // Log the escrow and commitment
+        sol_log("Instruction: CommitTake");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.commitment.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+};
+
+use crate::{
+    AccountCheck, ESCROW_SEED_PREFIX, Escrow, EscrowError, ProgramAccount, SignerAccount,
+    assert_is_maker, read_bytes,
+};
+
+pub struct ExtendExpiryAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExtendExpiryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+
+        // Return the accounts
+        Ok(Self { maker, escrow })
+    }
+}
+
+pub struct ExtendExpiryInstructionData {
+    pub expiry: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ExtendExpiryInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<i64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let expiry = i64::from_le_bytes(read_bytes(data, 0)?);
+
+        Ok(Self { expiry })
+    }
+}
+
+pub struct ExtendExpiry<'a> {
+    pub accounts: ExtendExpiryAccounts<'a>,
+    pub instruction_data: ExtendExpiryInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ExtendExpiry<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ExtendExpiryAccounts::try_from(accounts)?;
+        let instruction_data = ExtendExpiryInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ExtendExpiry<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+
+        // Check if the maker signed for the escrow
+        assert_is_maker(escrow, self.accounts.maker)?;
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // An expiry of zero never expires, so it can't be extended and isn't a later expiry
+        if escrow.expiry == 0 || self.instruction_data.expiry <= escrow.expiry {
+            return Err(EscrowError::ExpiryNotExtended.into());
+        }
+
+        escrow.set_expiry(self.instruction_data.expiry);
+
+        // Log the escrow and new expiry
+        sol_log("Instruction: ExtendExpiry");
+        pubkey::log(self.accounts.escrow.key());
+        sol_log_64(self.instruction_data.expiry as u64, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Escrow, EscrowError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    fn make_escrow(litesvm: &mut LiteSVM, maker: &Keypair, expiry: i64) -> Pubkey {
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                expiry.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        escrow_pda
+    }
+
+    fn extend_expiry_ix(maker: &Pubkey, escrow: &Pubkey, expiry: i64) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(*maker, true),
+                AccountMeta::new(*escrow, false),
+            ],
+            data: [vec![22u8], expiry.to_le_bytes().to_vec()].concat(),
+        }
+    }
+
+    #[test]
+    fn extend_expiry() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let attacker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let expiry: i64 = 1_000_000_000_000;
+        let new_expiry = expiry + 86_400;
+        let escrow_pda = make_escrow(litesvm, &maker, expiry);
+
+        // Only the maker can extend the escrow
+        simulate_expect_error(
+            litesvm,
+            &[&attacker],
+            &attacker.pubkey(),
+            &[extend_expiry_ix(
+                &attacker.pubkey(),
+                &escrow_pda,
+                new_expiry,
+            )],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[extend_expiry_ix(&maker.pubkey(), &escrow_pda, new_expiry)],
+        );
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.expiry, new_expiry);
+    }
+
+    #[test]
+    fn extend_expiry_with_earlier_expiry() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let expiry: i64 = 1_000_000_000_000;
+        let escrow_pda = make_escrow(litesvm, &maker, expiry);
+
+        // Shortening the expiry, or leaving it unchanged, is rejected
+        for new_expiry in [expiry - 1, expiry, 0] {
+            simulate_expect_error(
+                litesvm,
+                &[&maker],
+                &maker.pubkey(),
+                &[extend_expiry_ix(&maker.pubkey(), &escrow_pda, new_expiry)],
+                EscrowError::ExpiryNotExtended.into(),
+            );
+        }
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.expiry, expiry);
+    }
+}
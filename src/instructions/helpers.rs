@@ -1,9 +1,10 @@
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    cpi::invoke,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{Pubkey, create_program_address, find_program_address},
     sysvars::{Sysvar, rent::Rent},
 };
 use pinocchio_associated_token_account::instructions::Create;
@@ -12,8 +13,9 @@ use pinocchio_token::{
     instructions::{InitializeAccount3, InitializeMint2},
     state::{Mint, TokenAccount as TokenAccountState},
 };
+use solana_sha256_hasher::hashv;
 
-use crate::{Escrow, PinocchioError};
+use crate::{CONFIG_SEED, Commitment, Config, Escrow, EscrowError, PinocchioError};
 
 pub trait AccountCheck {
     fn check(account: &AccountInfo) -> Result<(), ProgramError>;
@@ -42,6 +44,42 @@ impl AccountCheck for SystemAccount {
     }
 }
 
+pub struct SystemProgram;
+
+impl AccountCheck for SystemProgram {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key().ne(&pinocchio_system::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct TokenProgramInterface;
+
+impl AccountCheck for TokenProgramInterface {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key().ne(&pinocchio_token::ID) && account.key().ne(&TOKEN_2022_PROGRAM_ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct AssociatedTokenProgram;
+
+impl AccountCheck for AssociatedTokenProgram {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key().ne(&pinocchio_associated_token_account::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(())
+    }
+}
+
 pub struct MintAccount;
 
 impl AccountCheck for MintAccount {
@@ -194,10 +232,50 @@ pub const TOKEN_2022_PROGRAM_ID: [u8; 32] = [
     0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
 ];
 
+// So11111111111111111111111111111111111111112
+pub const NATIVE_MINT: [u8; 32] = [
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84, 0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55, 0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+// BPFLoaderUpgradeab1e11111111111111111111111
+pub const BPF_LOADER_UPGRADEABLE_ID: [u8; 32] = [
+    0x02, 0xa8, 0xf6, 0x91, 0x4e, 0x88, 0xa1, 0xb0, 0xe2, 0x10, 0x15, 0x3e, 0xf7, 0x63, 0xae, 0x2b,
+    0x00, 0xc2, 0xb9, 0x3d, 0x16, 0xc1, 0x24, 0xd2, 0xc0, 0x53, 0x7a, 0x10, 0x04, 0x80, 0x00, 0x00,
+];
+
+// ProgramData accounts hold a u32 state tag, the deploy slot and an optional upgrade authority
+const PROGRAM_DATA_STATE: u32 = 3;
+const PROGRAM_DATA_AUTHORITY_OPTION_OFFSET: usize = 12;
+const PROGRAM_DATA_AUTHORITY_OFFSET: usize = 13;
+const PROGRAM_DATA_METADATA_LEN: usize = PROGRAM_DATA_AUTHORITY_OFFSET + 32;
+
 const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_ACCOUNT_STATE_FROZEN: u8 = 2;
+const TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET: usize = 121;
 pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
 pub const TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR: u8 = 0x02;
 
+// Token-2022 mint extension types
+pub const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+pub const MINT_CLOSE_AUTHORITY_EXTENSION: u16 = 3;
+pub const DEFAULT_ACCOUNT_STATE_EXTENSION: u16 = 6;
+pub const INTEREST_BEARING_CONFIG_EXTENSION: u16 = 10;
+pub const PERMANENT_DELEGATE_EXTENSION: u16 = 12;
+pub const TRANSFER_HOOK_EXTENSION: u16 = 14;
+pub const METADATA_POINTER_EXTENSION: u16 = 18;
+pub const TOKEN_METADATA_EXTENSION: u16 = 19;
+
+// Token-2022 instruction tags
+const TRANSFER_FEE_EXTENSION_INSTRUCTION: u8 = 26;
+const HARVEST_WITHHELD_TOKENS_TO_MINT_INSTRUCTION: u8 = 4;
+
 pub struct Mint2022Account;
 
 impl AccountCheck for Mint2022Account {
@@ -209,7 +287,9 @@ impl AccountCheck for Mint2022Account {
         let data = account.try_borrow_data()?;
 
         if data.len().ne(&Mint::LEN)
-            && data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET].ne(&TOKEN_2022_MINT_DISCRIMINATOR)
+            && data
+                .get(TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET)
+                .ne(&Some(&TOKEN_2022_MINT_DISCRIMINATOR))
         {
             return Err(ProgramError::InvalidAccountData);
         }
@@ -270,8 +350,9 @@ impl AccountCheck for TokenAccount2022Account {
         let data = account.try_borrow_data()?;
 
         if data.len().ne(&TokenAccountState::LEN)
-            && data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET]
-                .ne(&TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR)
+            && data
+                .get(TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET)
+                .ne(&Some(&TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR))
         {
             return Err(ProgramError::InvalidAccountData);
         }
@@ -333,7 +414,9 @@ impl AccountCheck for MintInterface {
             let data = account.try_borrow_data()?;
 
             if data.len().ne(&Mint::LEN)
-                && data[TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET].ne(&TOKEN_2022_MINT_DISCRIMINATOR)
+                && data
+                    .get(TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET)
+                    .ne(&Some(&TOKEN_2022_MINT_DISCRIMINATOR))
             {
                 return Err(ProgramError::InvalidAccountData);
             }
@@ -343,6 +426,98 @@ impl AccountCheck for MintInterface {
     }
 }
 
+impl MintInterface {
+    // Rejects Token-2022 mints carrying an extension that isn't in the allowed set
+    pub fn check_extensions(account: &AccountInfo, allowed: &[u16]) -> Result<(), ProgramError> {
+        if account.owner().ne(&TOKEN_2022_PROGRAM_ID) {
+            return Ok(());
+        }
+
+        let data = account.try_borrow_data()?;
+        let mut offset = TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET + 1;
+
+        while offset + 4 <= data.len() {
+            let extension = u16::from_le_bytes([data[offset], data[offset + 1]]);
+            let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+
+            // Uninitialized space marks the end of the extensions
+            if extension == 0 {
+                break;
+            }
+
+            if !allowed.contains(&extension) {
+                return Err(EscrowError::UnsupportedMintExtension.into());
+            }
+
+            offset += 4 + length;
+        }
+
+        Ok(())
+    }
+
+    // Checks if a Token-2022 mint carries the given extension
+    pub fn has_extension(account: &AccountInfo, extension: u16) -> Result<bool, ProgramError> {
+        if account.owner().ne(&TOKEN_2022_PROGRAM_ID) {
+            return Ok(false);
+        }
+
+        let data = account.try_borrow_data()?;
+        let mut offset = TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET + 1;
+
+        while offset + 4 <= data.len() {
+            let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+            let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+
+            // Uninitialized space marks the end of the extensions
+            if extension_type == 0 {
+                break;
+            }
+
+            if extension_type == extension {
+                return Ok(true);
+            }
+
+            offset += 4 + length;
+        }
+
+        Ok(false)
+    }
+
+    pub fn decimals(account: &AccountInfo) -> Result<u8, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(MINT_DECIMALS_OFFSET)
+            .copied()
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+// Moves the transfer fees withheld in a Token-2022 account into its mint
+pub struct HarvestWithheldTokensToMint<'a> {
+    pub mint: &'a AccountInfo,
+    pub source: &'a AccountInfo,
+}
+
+impl HarvestWithheldTokensToMint<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.source.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: &TOKEN_2022_PROGRAM_ID,
+            accounts: &account_metas,
+            data: &[
+                TRANSFER_FEE_EXTENSION_INSTRUCTION,
+                HARVEST_WITHHELD_TOKENS_TO_MINT_INSTRUCTION,
+            ],
+        };
+
+        invoke(&instruction, &[self.mint, self.source])
+    }
+}
+
 pub struct TokenAccountInterface;
 
 impl AccountCheck for TokenAccountInterface {
@@ -356,7 +531,11 @@ impl AccountCheck for TokenAccountInterface {
         } else {
             let data = account.try_borrow_data()?;
 
-            if data.len().ne(&TokenAccountState::LEN) {
+            if data.len().ne(&TokenAccountState::LEN)
+                && data
+                    .get(TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET)
+                    .ne(&Some(&TOKEN_2022_TOKEN_ACCOUNT_DISCRIMINATOR))
+            {
                 return Err(ProgramError::InvalidAccountData);
             }
         }
@@ -365,6 +544,67 @@ impl AccountCheck for TokenAccountInterface {
     }
 }
 
+impl TokenAccountInterface {
+    pub fn mint(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn owner(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    // The delegate is stored as a COption, a 4-byte tag followed by the key
+    pub fn delegate(account: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        let tag = data
+            .get(TOKEN_ACCOUNT_DELEGATE_OFFSET..TOKEN_ACCOUNT_DELEGATE_OFFSET + 4)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if tag == [0u8; 4] {
+            return Ok(None);
+        }
+
+        data.get(TOKEN_ACCOUNT_DELEGATE_OFFSET + 4..TOKEN_ACCOUNT_DELEGATE_OFFSET + 36)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Some)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn delegated_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET..TOKEN_ACCOUNT_DELEGATED_AMOUNT_OFFSET + 8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn is_frozen(account: &AccountInfo) -> Result<bool, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        data.get(TOKEN_ACCOUNT_STATE_OFFSET)
+            .map(|state| state.eq(&TOKEN_ACCOUNT_STATE_FROZEN))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
 pub trait AssociatedTokenAccountCheck {
     fn check(
         account: &AccountInfo,
@@ -382,7 +622,12 @@ impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
         mint: &AccountInfo,
         token_program: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        TokenAccount::check(account)?;
+        TokenAccountInterface::check(account)?;
+
+        // The account and its mint must both belong to the token program being used
+        if account.owner().ne(token_program.key()) || mint.owner().ne(token_program.key()) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
 
         if find_program_address(
             &[authority.key(), token_program.key(), mint.key()],
@@ -394,6 +639,13 @@ impl AssociatedTokenAccountCheck for AssociatedTokenAccount {
             return Err(PinocchioError::InvalidAddress.into());
         }
 
+        // Check the stored mint and owner as well as the address
+        if TokenAccountInterface::mint(account)?.ne(mint.key())
+            || TokenAccountInterface::owner(account)?.ne(authority.key())
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
         Ok(())
     }
 }
@@ -445,10 +697,17 @@ impl AssociatedTokenAccountInit for AssociatedTokenAccount {
         system_program: &AccountInfo,
         token_program: &AccountInfo,
     ) -> ProgramResult {
-        match Self::check(account, payer, mint, token_program) {
-            Ok(_) => Ok(()),
-            Err(_) => Self::init(account, mint, payer, owner, system_program, token_program),
+        // Create the account if it doesn't exist yet
+        if account.data_is_empty() {
+            return Self::init(account, mint, payer, owner, system_program, token_program);
         }
+
+        // An existing account must already be the owner's ATA for this mint
+        if Self::check(account, owner, mint, token_program).is_err() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        Ok(())
     }
 }
 
@@ -468,6 +727,114 @@ impl AccountCheck for ProgramAccount {
     }
 }
 
+impl ProgramAccount {
+    // A closed account has been drained and handed back to the system program
+    pub fn is_closed(account: &AccountInfo) -> bool {
+        account.lamports() == 0 && account.owner().eq(&pinocchio_system::ID)
+    }
+}
+
+pub struct ProgramDataAccount;
+
+impl AccountCheck for ProgramDataAccount {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.owner().ne(&BPF_LOADER_UPGRADEABLE_ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // Only this program's ProgramData account, not that of any other upgradeable program
+        let (program_data_key, _) = find_program_address(&[&crate::ID], &BPF_LOADER_UPGRADEABLE_ID);
+        if account.key().ne(&program_data_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(())
+    }
+}
+
+impl ProgramDataAccount {
+    // None once the program has been made immutable
+    pub fn upgrade_authority(account: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+        let data = account.try_borrow_data()?;
+
+        // Check the length up front, the slices below index the account data directly
+        if data.len() <= PROGRAM_DATA_AUTHORITY_OPTION_OFFSET
+            || data[..4] != PROGRAM_DATA_STATE.to_le_bytes()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match data[PROGRAM_DATA_AUTHORITY_OPTION_OFFSET] {
+            0 => Ok(None),
+            1 if data.len() >= PROGRAM_DATA_METADATA_LEN => Ok(Some(
+                data[PROGRAM_DATA_AUTHORITY_OFFSET..PROGRAM_DATA_METADATA_LEN]
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidAccountData)?,
+            )),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+pub struct ConfigAccount;
+
+impl AccountCheck for ConfigAccount {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        if account.data_len().ne(&Config::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigAccount {
+    // The config has to be initialized, so a missing one can't be used to skip the switch
+    pub fn check_not_paused(account: &AccountInfo) -> ProgramResult {
+        let data = account.try_borrow_data()?;
+        let config = Config::load(&data)?;
+
+        let config_key = create_program_address(&[CONFIG_SEED, &config.bump], &crate::ID)
+            .map_err(|_| EscrowError::InvalidConfig)?;
+        if &config_key != account.key() {
+            return Err(EscrowError::InvalidConfig.into());
+        }
+
+        if config.paused {
+            return Err(EscrowError::Paused.into());
+        }
+
+        Ok(())
+    }
+}
+
+pub struct CommitmentAccount;
+
+impl AccountCheck for CommitmentAccount {
+    fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        if account.data_len().ne(&Commitment::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
+
+impl CommitmentAccount {
+    // Binds the commitment to the taker, so a revealed nonce can't be replayed by anyone else
+    pub fn hash(taker: &Pubkey, nonce: &[u8; 32]) -> [u8; 32] {
+        hashv(&[taker.as_slice(), nonce.as_slice()]).to_bytes()
+    }
+}
+
 pub trait ProgramAccountInit {
     fn init<'a, T: Sized>(
         payer: &AccountInfo,
@@ -517,3 +884,30 @@ impl AccountClose for ProgramAccount {
         account.close()
     }
 }
+
+// Reads a fixed-size field from instruction data, erroring instead of panicking when it's short
+#[inline(always)]
+pub fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+// Splits trailing accounts into fixed-size groups, such as one group per basket entry
+#[inline(always)]
+pub fn account_groups<const N: usize>(
+    accounts: &[AccountInfo],
+) -> impl Iterator<Item = &[AccountInfo; N]> {
+    accounts
+        .chunks_exact(N)
+        .filter_map(|group| group.try_into().ok())
+}
+
+// Checks the signer is the maker of the escrow, for instructions only the maker may call
+#[inline(always)]
+pub fn assert_is_maker(escrow: &Escrow, signer: &AccountInfo) -> ProgramResult {
+    if !signer.is_signer() || escrow.maker.ne(signer.key()) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, find_program_address},
+};
+
+use crate::{
+    AccountCheck, CONFIG_SEED, Config, FEE_BPS, MIN_DEPOSIT_AMOUNT, ProgramAccount,
+    ProgramAccountInit, ProgramDataAccount, SignerAccount, SystemProgram, TREASURY,
+};
+
+pub struct InitConfigAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub program_data: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitConfigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, system_program, program_data] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        SignerAccount::check(admin)?;
+        ProgramDataAccount::check(program_data)?;
+
+        // Return the accounts
+        Ok(Self {
+            admin,
+            config,
+            system_program,
+            program_data,
+        })
+    }
+}
+
+pub struct InitConfig<'a> {
+    pub accounts: InitConfigAccounts<'a>,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = InitConfigAccounts::try_from(accounts)?;
+
+        // Only the upgrade authority can become the admin, so the deployment can't be front-run
+        if ProgramDataAccount::upgrade_authority(accounts.program_data)?.as_ref()
+            != Some(accounts.admin.key())
+        {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Initialize the config, which fails if it already exists
+        let (_, bump) = find_program_address(&[CONFIG_SEED], &crate::ID);
+
+        let bump_binding = [bump];
+        let config_seeds = [Seed::from(CONFIG_SEED), Seed::from(&bump_binding)];
+
+        ProgramAccount::init::<Config>(
+            accounts.admin,
+            accounts.config,
+            &config_seeds,
+            Config::LEN,
+        )?;
+
+        Ok(Self { accounts, bump })
+    }
+}
+
+impl<'a> InitConfig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    // The program's upgrade authority becomes the admin
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut_uninitialized(data.as_mut())?;
+
+        config.set_inner(
+            *self.accounts.admin.key(),
+            TREASURY,
+            FEE_BPS,
+            MIN_DEPOSIT_AMOUNT,
+            [self.bump],
+        );
+
+        // Log the config and admin
+        sol_log("Instruction: InitConfig");
+        pubkey::log(self.accounts.config.key());
+        pubkey::log(self.accounts.admin.key());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_account::Account;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        CONFIG_ACCOUNT_LEN, Config, FEE_BPS, MIN_DEPOSIT_AMOUNT, TREASURY,
+        tests::{
+            constants::{BPF_LOADER_UPGRADEABLE_ID, PROGRAM_ID, SYSTEM_PROGRAM_ID},
+            pda::{get_config_pda, get_program_data_address},
+            utils::{
+                build_and_send_transaction, init_wallet, set_upgrade_authority,
+                setup_without_config, simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn init_config() {
+        let (litesvm, default_payer) = &mut setup_without_config();
+
+        let config_pda = get_config_pda();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(default_payer.pubkey(), true),
+                AccountMeta::new(config_pda, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_program_data_address(), false),
+            ],
+            data: vec![13u8],
+        };
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            core::slice::from_ref(&ix),
+        );
+
+        assert!(result.is_ok());
+
+        let config_acc = litesvm.get_account(&config_pda).unwrap();
+        let config = Config::load(config_acc.data.as_ref()).unwrap();
+
+        assert_eq!(config.discriminator, Config::DISCRIMINATOR);
+        assert_eq!(config.admin, default_payer.pubkey().to_bytes());
+        assert_eq!(config.fee_recipient, TREASURY);
+        assert_eq!(config.fee_bps(), FEE_BPS);
+        assert_eq!(config.min_deposit(), MIN_DEPOSIT_AMOUNT);
+        assert!(!config.paused);
+        assert_eq!(config_acc.data.len(), CONFIG_ACCOUNT_LEN);
+
+        // The config can only be initialized once
+        litesvm.expire_blockhash();
+
+        let result =
+            build_and_send_transaction(litesvm, &[default_payer], &default_payer.pubkey(), &[ix]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn init_config_without_upgrade_authority() {
+        let (litesvm, _default_payer) = &mut setup_without_config();
+
+        let attacker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+
+        let init_config_ix = |admin| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(admin, true),
+                AccountMeta::new(get_config_pda(), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_program_data_address(), false),
+            ],
+            data: vec![13u8],
+        };
+
+        // Only the upgrade authority can claim the admin role
+        simulate_expect_error(
+            litesvm,
+            &[&attacker],
+            &attacker.pubkey(),
+            &[init_config_ix(attacker.pubkey())],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        // Nobody can once the program has been made immutable
+        set_upgrade_authority(litesvm, None);
+
+        simulate_expect_error(
+            litesvm,
+            &[&attacker],
+            &attacker.pubkey(),
+            &[init_config_ix(attacker.pubkey())],
+            ProgramError::MissingRequiredSignature,
+        );
+    }
+    #[test]
+    fn init_config_with_truncated_program_data() {
+        let (litesvm, default_payer) = &mut setup_without_config();
+
+        // The authority option is set but the account ends before the authority itself
+        let data = [
+            3u32.to_le_bytes().to_vec(),
+            0u64.to_le_bytes().to_vec(),
+            vec![1u8],
+            default_payer.pubkey().to_bytes()[..16].to_vec(),
+        ]
+        .concat();
+        let lamports = litesvm.minimum_balance_for_rent_exemption(data.len());
+
+        litesvm
+            .set_account(
+                get_program_data_address(),
+                Account {
+                    lamports,
+                    data,
+                    owner: BPF_LOADER_UPGRADEABLE_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+
+        simulate_expect_error(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(default_payer.pubkey(), true),
+                    AccountMeta::new(get_config_pda(), false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(get_program_data_address(), false),
+                ],
+                data: vec![13u8],
+            }],
+            ProgramError::InvalidAccountData,
+        );
+    }
+}
@@ -1,13 +1,23 @@
 use core::mem::size_of;
 use pinocchio::{
-    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
-    pubkey::find_program_address,
+    ProgramResult,
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64, sol_log_data},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey, find_program_address},
+    sysvars::{Sysvar, rent::Rent},
 };
-use pinocchio_token::instructions::Transfer;
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token_2022::instructions::{SyncNative, TransferChecked};
 
 use crate::{
-    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, AssociatedTokenAccountInit,
-    Escrow, MintInterface, ProgramAccount, ProgramAccountInit, SignerAccount,
+    ALLOWED_MINT_EXTENSIONS, AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, AssociatedTokenProgram, Config, ConfigAccount, ESCROW_SEED_PREFIX,
+    Escrow, EscrowError, MAX_BASKET_ENTRIES, MintInterface, NATIVE_MINT, ProgramAccount,
+    ProgramAccountInit, SignerAccount, SystemAccount, SystemProgram, TokenAccountInterface,
+    TokenProgramInterface, account_groups, read_bytes,
 };
 
 pub struct MakeAccounts<'a> {
@@ -20,6 +30,9 @@ pub struct MakeAccounts<'a> {
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub associated_token_account_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+    pub basket: &'a [AccountInfo],
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for MakeAccounts<'a> {
@@ -36,16 +49,61 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MakeAccounts<'a> {
             system_program,
             token_program,
             associated_token_account_program,
+            config,
+            remaining_accounts @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        // An optional payer funds the new accounts instead of the maker, followed by a mint,
+        // maker ATA and vault for each basket entry, anything left over is surplus
+        let (payer, basket) = match (remaining_accounts.len() % 3, remaining_accounts) {
+            (0, basket) => (maker, basket),
+            (1, [payer, basket @ ..]) => {
+                SignerAccount::check(payer)?;
+                (payer, basket)
+            }
+            _ => return Err(EscrowError::TooManyAccountKeys.into()),
+        };
+
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        ConfigAccount::check(config)?;
+        // Only the signer flag is checked, so a PDA maker signed through invoke_signed is accepted,
+        // and one holding data passes a separate payer since it can't fund system accounts
         SignerAccount::check(maker)?;
         MintInterface::check(mint_a)?;
         MintInterface::check(mint_b)?;
-        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        MintInterface::check_extensions(mint_a, ALLOWED_MINT_EXTENSIONS)?;
+
+        // Swapping a mint for itself is rejected, the taker's ATAs A and B would be one account
+        if mint_a.key().eq(mint_b.key()) {
+            return Err(EscrowError::SameMint.into());
+        }
+
+        // Wrapped SOL ATAs are created in Make if the maker doesn't have one yet
+        if mint_a.key().ne(&NATIVE_MINT) {
+            AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+        }
+
+        // Basket mints follow the same rules as mint A and must all be distinct
+        for (index, [mint, maker_ata, _]) in account_groups::<3>(basket).enumerate() {
+            MintInterface::check(mint)?;
+            MintInterface::check_extensions(mint, ALLOWED_MINT_EXTENSIONS)?;
+            AssociatedTokenAccount::check(maker_ata, maker, mint, token_program)?;
+
+            if mint.key().eq(mint_a.key())
+                || mint.key().eq(mint_b.key())
+                || account_groups::<3>(basket)
+                    .take(index)
+                    .any(|[other, _, _]| other.key().eq(mint.key()))
+            {
+                return Err(EscrowError::SameMint.into());
+            }
+        }
 
         // Return the accounts
         Ok(Self {
@@ -58,6 +116,9 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MakeAccounts<'a> {
             system_program,
             token_program,
             associated_token_account_program,
+            config,
+            payer,
+            basket,
         })
     }
 }
@@ -66,37 +127,121 @@ pub struct MakeInstructionData {
     pub seed: u64,
     pub receive: u64,
     pub amount: u64,
+    pub expiry: i64,
+    pub start_time: i64,
+    pub vesting_start: i64,
+    pub vesting_duration: i64,
+    pub authorized_taker: Pubkey,
+    pub vault_rent_recipient: Pubkey,
+    pub memo: [u8; 32],
+    pub basket_amounts: [u64; MAX_BASKET_ENTRIES],
+    pub basket_len: usize,
 }
 
-impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
-    type Error = ProgramError;
+impl MakeInstructionData {
+    // Seed, receive and amount only, every later field is left at its default
+    pub const VERSION_1: u8 = 1;
+    // Every field up to the memo, followed by the basket amounts
+    pub const VERSION_2: u8 = 2;
 
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() * 3 {
+    pub const V1_LEN: usize = size_of::<u64>() * 3;
+
+    // Length of the version 2 payload after the discriminator and version bytes have been
+    // stripped, not counting the amount appended for each basket entry
+    pub const LEN: usize = size_of::<u64>() * 3
+        + size_of::<i64>() * 4
+        + size_of::<Pubkey>() * 2
+        + size_of::<[u8; 32]>();
+
+    fn parse(data: &[u8]) -> Result<Self, ProgramError> {
+        let basket_data = data
+            .get(Self::LEN..)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if basket_data.len() % size_of::<u64>() != 0
+            || basket_data.len() / size_of::<u64>() > MAX_BASKET_ENTRIES
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let receive = u64::from_le_bytes(data[8..16].try_into().unwrap());
-        let amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let mut amounts = [0u64; 3];
+        for (index, value) in amounts.iter_mut().enumerate() {
+            *value = u64::from_le_bytes(read_bytes(data, index * size_of::<u64>())?);
+        }
+        let [seed, receive, amount] = amounts;
+
+        let expiry = i64::from_le_bytes(read_bytes(data, 24)?);
+        let start_time = i64::from_le_bytes(read_bytes(data, 32)?);
+        let vesting_start = i64::from_le_bytes(read_bytes(data, 40)?);
+        let vesting_duration = i64::from_le_bytes(read_bytes(data, 48)?);
+        let authorized_taker: Pubkey = read_bytes(data, 56)?;
+        let vault_rent_recipient: Pubkey = read_bytes(data, 88)?;
+        let memo: [u8; 32] = read_bytes(data, 120)?;
+
+        let basket_len = basket_data.len() / size_of::<u64>();
+        let mut basket_amounts = [0u64; MAX_BASKET_ENTRIES];
+        for (index, value) in basket_amounts.iter_mut().take(basket_len).enumerate() {
+            *value = u64::from_le_bytes(read_bytes(basket_data, index * size_of::<u64>())?);
+        }
 
         // Instruction Checks
-        if amount == 0 {
+        if amount == 0 || receive == 0 || basket_amounts[..basket_len].contains(&0) {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        if vesting_duration < 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
 
+        // Vesting only releases token A, so it can't be combined with a basket
+        if vesting_duration != 0 && basket_len != 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
+
         Ok(Self {
             seed,
             receive,
             amount,
+            expiry,
+            start_time,
+            vesting_start,
+            vesting_duration,
+            authorized_taker,
+            vault_rent_recipient,
+            memo,
+            basket_amounts,
+            basket_len,
         })
     }
 }
 
+impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        match data.split_first() {
+            // Older layouts are a prefix of the latest, so they are zero-extended to it
+            Some((&Self::VERSION_1, data)) => {
+                if data.len() != Self::V1_LEN {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let mut extended = [0u8; Self::LEN];
+                extended[..Self::V1_LEN].copy_from_slice(data);
+
+                Self::parse(&extended)
+            }
+            Some((&Self::VERSION_2, data)) => Self::parse(data),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
 pub struct Make<'a> {
     pub accounts: MakeAccounts<'a>,
     pub instruction_data: MakeInstructionData,
     pub bump: u8,
+    // Set by MakeDelegated to move the deposit with the escrow as the maker ATA's delegate
+    pub delegated: bool,
 }
 
 impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
@@ -106,46 +251,114 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
         let accounts = MakeAccounts::try_from(accounts)?;
         let instruction_data = MakeInstructionData::try_from(data)?;
 
-        // Initialize the Accounts needed
-        let (_, bump) = find_program_address(
+        // Check if there is a mint, maker ATA and vault for each basket amount
+        if accounts.basket.len() != instruction_data.basket_len * 3 {
+            return Err(EscrowError::BasketMismatch.into());
+        }
+
+        // Check if the protocol is paused before creating any accounts
+        ConfigAccount::check_not_paused(accounts.config)?;
+
+        // Check if the deposit meets the minimum the admin set for this deployment
+        if instruction_data.amount
+            < Config::load(&accounts.config.try_borrow_data()?)?.min_deposit()
+        {
+            return Err(EscrowError::DepositTooSmall.into());
+        }
+
+        // Initialize the Accounts needed, only the canonical bump is accepted so a maker and seed
+        // can't open escrows at more than one address
+        let (escrow_key, bump) = find_program_address(
             &[
-                b"escrow",
+                ESCROW_SEED_PREFIX,
                 accounts.maker.key(),
                 &instruction_data.seed.to_le_bytes(),
             ],
             &crate::ID,
         );
+        if &escrow_key != accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
 
         let seed_binding = instruction_data.seed.to_le_bytes();
         let bump_binding = [bump];
         let escrow_seeds = [
-            Seed::from(b"escrow"),
+            Seed::from(ESCROW_SEED_PREFIX),
             Seed::from(accounts.maker.key().as_ref()),
             Seed::from(&seed_binding),
             Seed::from(&bump_binding),
         ];
 
+        // Check if the escrow is new, so a reused seed fails clearly instead of inside the CPI
+        if SystemAccount::check(accounts.escrow).is_err() || !accounts.escrow.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
         ProgramAccount::init::<Escrow>(
-            accounts.maker,
+            accounts.payer,
             accounts.escrow,
             &escrow_seeds,
             Escrow::LEN,
         )?;
 
+        // Initialize the wrapped SOL account of the maker
+        if accounts.mint_a.key().eq(&NATIVE_MINT) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_a,
+                accounts.mint_a,
+                accounts.payer,
+                accounts.maker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
         // Initialize the vault
         AssociatedTokenAccount::init(
             accounts.vault,
             accounts.mint_a,
-            accounts.maker,
+            accounts.payer,
             accounts.escrow,
             accounts.system_program,
             accounts.token_program,
         )?;
 
+        // Check if the vault is owned by the escrow and holds mint A
+        AssociatedTokenAccount::check(
+            accounts.vault,
+            accounts.escrow,
+            accounts.mint_a,
+            accounts.token_program,
+        )?;
+
+        // Initialize a vault for each basket entry
+        for [mint, _, vault] in account_groups::<3>(accounts.basket) {
+            AssociatedTokenAccount::init(
+                vault,
+                mint,
+                accounts.payer,
+                accounts.escrow,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        // Check if the new accounts were funded to be rent exempt
+        let rent = Rent::get()?;
+        for account in [accounts.escrow, accounts.vault]
+            .into_iter()
+            .chain(account_groups::<3>(accounts.basket).map(|[_, _, vault]| vault))
+        {
+            if account.lamports() < rent.minimum_balance(account.data_len()) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+        }
+
         Ok(Self {
             accounts,
             instruction_data,
             bump,
+            delegated: false,
         })
     }
 }
@@ -154,9 +367,22 @@ impl<'a> Make<'a> {
     pub const DISCRIMINATOR: &'a u8 = &0;
 
     pub fn process(&mut self) -> ProgramResult {
+        // Snapshot the protocol fee, so Take charges the rate in force when the escrow was opened
+        let fee_bps = Config::load(&self.accounts.config.try_borrow_data()?)?.fee_bps();
+
         // Populate the escrow account
         let mut data = self.accounts.escrow.try_borrow_mut_data()?;
-        let escrow = Escrow::load_mut(data.as_mut())?;
+        let escrow = Escrow::load_mut_uninitialized(data.as_mut())?;
+
+        // Refund the vault rent to the maker unless another recipient is set
+        let vault_rent_recipient = if self.instruction_data.vault_rent_recipient == [0u8; 32] {
+            *self.accounts.maker.key()
+        } else {
+            self.instruction_data.vault_rent_recipient
+        };
+
+        // Escrows asking for the native mint are paid in lamports through TakeNative
+        let receive_native = self.accounts.mint_b.key().eq(&NATIVE_MINT);
 
         escrow.set_inner(
             self.instruction_data.seed,
@@ -164,17 +390,123 @@ impl<'a> Make<'a> {
             *self.accounts.mint_a.key(),
             *self.accounts.mint_b.key(),
             self.instruction_data.receive,
+            self.instruction_data.expiry,
+            self.instruction_data.start_time,
+            self.instruction_data.vesting_start,
+            self.instruction_data.vesting_duration,
+            self.instruction_data.authorized_taker,
+            vault_rent_recipient,
+            self.instruction_data.memo,
+            MintInterface::decimals(self.accounts.mint_a)?,
+            MintInterface::decimals(self.accounts.mint_b)?,
+            receive_native,
             [self.bump],
+            fee_bps,
         );
 
+        for (index, [mint, _, _]) in account_groups::<3>(self.accounts.basket).enumerate() {
+            escrow.set_basket_entry(
+                index,
+                *mint.key(),
+                self.instruction_data.basket_amounts[index],
+            );
+        }
+
+        drop(data);
+
+        // Wrap any native SOL the maker is short of the deposit
+        if self.accounts.mint_a.key().eq(&NATIVE_MINT) {
+            let wrapped = TokenAccountInterface::amount(self.accounts.maker_ata_a)?;
+            let lamports = self.instruction_data.amount.saturating_sub(wrapped);
+
+            if lamports > 0 {
+                Transfer {
+                    from: self.accounts.maker,
+                    to: self.accounts.maker_ata_a,
+                    lamports,
+                }
+                .invoke()?;
+
+                SyncNative {
+                    native_token: self.accounts.maker_ata_a,
+                    token_program: self.accounts.token_program.key(),
+                }
+                .invoke()?;
+            }
+        }
+
         // Transfer tokens to vault
-        Transfer {
+        let vault_before = TokenAccountInterface::amount(self.accounts.vault)?;
+        let transfer = TransferChecked {
             from: self.accounts.maker_ata_a,
+            mint: self.accounts.mint_a,
             to: self.accounts.vault,
-            authority: self.accounts.maker,
+            authority: if self.delegated {
+                self.accounts.escrow
+            } else {
+                self.accounts.maker
+            },
             amount: self.instruction_data.amount,
+            decimals: MintInterface::decimals(self.accounts.mint_a)?,
+            token_program: self.accounts.token_program.key(),
+        };
+
+        if self.delegated {
+            let seed_binding = self.instruction_data.seed.to_le_bytes();
+            let bump_binding = [self.bump];
+            let escrow_seeds = [
+                Seed::from(ESCROW_SEED_PREFIX),
+                Seed::from(self.accounts.maker.key().as_ref()),
+                Seed::from(&seed_binding),
+                Seed::from(&bump_binding),
+            ];
+
+            transfer.invoke_signed(&[Signer::from(&escrow_seeds)])?;
+        } else {
+            transfer.invoke()?;
+        }
+
+        // Transfer fee mints withhold part of the deposit, so report what actually arrived
+        let arrived = TokenAccountInterface::amount(self.accounts.vault)?
+            .checked_sub(vault_before)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Transfer each basket token to its vault
+        for (index, [mint, maker_ata, vault]) in
+            account_groups::<3>(self.accounts.basket).enumerate()
+        {
+            TransferChecked {
+                from: maker_ata,
+                mint,
+                to: vault,
+                authority: self.accounts.maker,
+                amount: self.instruction_data.basket_amounts[index],
+                decimals: MintInterface::decimals(mint)?,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke()?;
+        }
+
+        // Log the escrow, mints, seed, deposit and receive amounts
+        sol_log("Instruction: Make");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.maker.key());
+        pubkey::log(self.accounts.mint_a.key());
+        pubkey::log(self.accounts.mint_b.key());
+        sol_log_64(
+            self.instruction_data.seed,
+            self.instruction_data.amount,
+            self.instruction_data.receive,
+            arrived,
+            0,
+        );
+
+        // The memo is logged as raw bytes since it isn't required to be UTF-8
+        if self.instruction_data.memo != [0u8; 32] {
+            sol_log_data(&[&self.instruction_data.memo]);
         }
-        .invoke()?;
+
+        set_return_data(&arrived.to_le_bytes());
 
         Ok(())
     }
@@ -182,22 +514,33 @@ impl<'a> Make<'a> {
 
 #[cfg(test)]
 mod tests {
-    use solana_instruction::{AccountMeta, Instruction};
+    use pinocchio::program_error::ProgramError;
+    use solana_account::Account;
+    use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
+    use solana_pubkey::Pubkey;
     use solana_signer::Signer;
+    use solana_transaction_error::TransactionError;
     use spl_associated_token_account::{
         get_associated_token_address_with_program_id,
         solana_program::native_token::LAMPORTS_PER_SOL,
     };
+    use spl_token_2022::state::{Account as TokenAccount, AccountState, Mint};
 
     use crate::{
-        Escrow,
+        ESCROW_ACCOUNT_LEN, Escrow, EscrowError, MIN_DEPOSIT_AMOUNT, MakeInstructionData,
+        VAULT_ACCOUNT_LEN, escrow_space,
         tests::{
             constants::{
-                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
-                TOKEN_PROGRAM_ID,
+                ASSOCIATED_TOKEN_PROGRAM_ID, CALLER_PROGRAM_ID, ESCROW_SEED, MINT_DECIMALS,
+                PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            },
+            pda::{get_caller_authority_pda, get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint,
+                init_mint_with_default_account_state, init_mint_with_permanent_delegate,
+                init_mint_with_transfer_fee, init_native_mint, init_wallet, setup,
+                simulate_expect_error,
             },
-            pda::get_escrow_pda,
-            utils::{build_and_send_transaction, init_ata, init_mint, init_wallet, setup},
         },
     };
 
@@ -213,17 +556,28 @@ mod tests {
         let seed = 42u64;
         let receive_amount: u64 = 100_000_000;
         let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
         let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
-        let vault =
-            get_associated_token_address_with_program_id(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
 
         let data = [
             vec![0u8],
+            vec![2u8],
             seed.to_le_bytes().to_vec(),
             receive_amount.to_le_bytes().to_vec(),
             give_amount.to_le_bytes().to_vec(),
+            expiry.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
         ]
         .concat();
+
+        assert_eq!(data.len(), 2 + MakeInstructionData::LEN);
+
         let ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
@@ -236,6 +590,7 @@ mod tests {
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
             ],
             data,
         };
@@ -245,10 +600,1706 @@ mod tests {
         let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
         let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
 
+        assert_eq!(escrow.discriminator, Escrow::DISCRIMINATOR);
         assert_eq!(escrow.seed, seed);
         assert_eq!(escrow.maker, maker.pubkey().to_bytes());
         assert_eq!(escrow.mint_a, mint_a.to_bytes());
         assert_eq!(escrow.mint_b, mint_b.to_bytes());
         assert_eq!(escrow.receive, receive_amount);
+        assert_eq!(escrow.expiry, expiry);
+        assert_eq!(escrow.start_time, 0);
+        assert_eq!(escrow.vesting_duration, 0);
+        assert_eq!(escrow.authorized_taker, [0u8; 32]);
+        assert_eq!(escrow.vault_rent_recipient, maker.pubkey().to_bytes());
+        assert!(escrow.basket().is_empty());
+        assert_eq!(escrow.mint_a_decimals, MINT_DECIMALS);
+
+        // The exported sizes match the accounts Make actually allocated
+        let vault_acc = litesvm.get_account(&vault).unwrap();
+
+        assert_eq!(escrow_acc.data.len(), ESCROW_ACCOUNT_LEN);
+        assert_eq!(escrow_acc.data.len(), escrow_space());
+        assert_eq!(vault_acc.data.len(), VAULT_ACCOUNT_LEN);
+    }
+
+    #[test]
+    fn make_stores_mint_decimals() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        // Mint A's decimals differ from mint B's so neither mint can satisfy the other's check
+        let mint_a_decimals = 9;
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, mint_a_decimals, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            expiry.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+
+        assert_eq!(data.len(), 2 + MakeInstructionData::LEN);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(
+            escrow.mint_a_decimals,
+            fetch_account::<Mint>(litesvm, &mint_a).decimals
+        );
+        assert_eq!(escrow.mint_a_decimals, mint_a_decimals);
+        assert_eq!(
+            escrow.mint_b_decimals,
+            fetch_account::<Mint>(litesvm, &mint_b).decimals
+        );
+        assert_eq!(escrow.mint_b_decimals, MINT_DECIMALS);
+    }
+
+    #[test]
+    fn make_with_memo() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let mut memo = [0u8; 32];
+        memo[..13].copy_from_slice(b"INV-2024-0042");
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            expiry.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            memo.to_vec(),
+        ]
+        .concat();
+
+        assert_eq!(data.len(), 2 + MakeInstructionData::LEN);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.memo, memo);
+    }
+
+    #[test]
+    fn make_accounts_are_rent_exempt() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            expiry.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+
+        assert_eq!(data.len(), 2 + MakeInstructionData::LEN);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        for address in [escrow_pda, vault] {
+            let account = litesvm.get_account(&address).unwrap();
+
+            assert!(
+                account.lamports >= litesvm.minimum_balance_for_rent_exemption(account.data.len())
+            );
+        }
+    }
+
+    #[test]
+    fn make_basket() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let give_amount_c: u64 = 250_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault_c = get_vault_address(&escrow_pda, &mint_c, &TOKEN_PROGRAM_ID);
+
+        // Mint C, the maker's ATA and the escrow's vault for it follow the fixed accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(maker_ata_c, false),
+                AccountMeta::new(vault_c, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                give_amount_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.basket().len(), 1);
+        assert_eq!(escrow.basket()[0].mint, mint_c.to_bytes());
+        assert_eq!(escrow.basket()[0].amount, give_amount_c);
+
+        let vault_bal = fetch_account::<TokenAccount>(litesvm, &vault).amount;
+        let vault_c_bal = fetch_account::<TokenAccount>(litesvm, &vault_c).amount;
+
+        assert_eq!(vault_bal, give_amount);
+        assert_eq!(vault_c_bal, give_amount_c);
+    }
+
+    #[test]
+    fn make_basket_without_basket_accounts() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let give_amount_c: u64 = 250_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                give_amount_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::BasketMismatch as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn make_with_separate_payer() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let payer = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            expiry.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new(payer.pubkey(), true),
+            ],
+            data,
+        };
+
+        let pre_maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+
+        let result = build_and_send_transaction(litesvm, &[&maker, &payer], &payer.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let post_maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+
+        assert_eq!(pre_maker_lamports, post_maker_lamports);
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.maker, maker.pubkey().to_bytes());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn make_with_pda_maker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        litesvm
+            .add_program_from_file(CALLER_PROGRAM_ID, "target/deploy/escrow_caller.so")
+            .unwrap();
+
+        let payer = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+
+        // The maker is a PDA of the caller program holding its state, like a governance account,
+        // so it can't fund new accounts and a separate payer is passed
+        let maker = get_caller_authority_pda();
+        let maker_ata_a = init_ata(litesvm, mint_a, maker, 1_000_000_000);
+        litesvm
+            .set_account(
+                maker,
+                Account {
+                    lamports: litesvm.minimum_balance_for_rent_exemption(8),
+                    data: vec![0u8; 8],
+                    owner: CALLER_PROGRAM_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let expiry: i64 = 0;
+        let escrow_pda = get_escrow_pda(&maker, seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        // The caller program signs for its PDA through invoke_signed
+        let ix = Instruction {
+            program_id: CALLER_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker, false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(PROGRAM_ID, false),
+            ],
+            data: [
+                seed.to_le_bytes(),
+                receive_amount.to_le_bytes(),
+                give_amount.to_le_bytes(),
+                expiry.to_le_bytes(),
+            ]
+            .concat(),
+        };
+
+        let pre_maker_acc = litesvm.get_account(&maker).unwrap();
+
+        let result = build_and_send_transaction(litesvm, &[&payer], &payer.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // The maker's lamports and state are untouched, the payer covered the rent
+        let post_maker_acc = litesvm.get_account(&maker).unwrap();
+
+        assert_eq!(pre_maker_acc.lamports, post_maker_acc.lamports);
+        assert_eq!(pre_maker_acc.data, post_maker_acc.data);
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.maker, maker.to_bytes());
+        assert_eq!(escrow.creator, maker.to_bytes());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn make_logs() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let logs = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix])
+            .unwrap()
+            .logs;
+
+        let expected_logs = [
+            "Program log: Instruction: Make".to_string(),
+            format!("Program log: {}", escrow_pda),
+            format!("Program log: {}", maker.pubkey()),
+            format!("Program log: {}", mint_a),
+            format!("Program log: {}", mint_b),
+            format!(
+                "Program log: {:#x}, {:#x}, {:#x}, {:#x}, {:#x}",
+                seed, give_amount, receive_amount, 0, 0
+            ),
+        ];
+
+        for expected_log in expected_logs {
+            assert!(logs.contains(&expected_log));
+        }
+    }
+
+    #[test]
+    fn make_with_zero_amount() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 0;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::ZeroAmount as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_below_minimum_deposit() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = MIN_DEPOSIT_AMOUNT - 1;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::DepositTooSmall as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_at_minimum_deposit() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = MIN_DEPOSIT_AMOUNT;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn make_with_existing_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            core::slice::from_ref(&ix),
+        );
+
+        assert!(result.is_ok());
+
+        // Reusing the seed hits the existing escrow
+        litesvm.expire_blockhash();
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn make_with_account_count_mismatch() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            100_000_000u64.to_le_bytes().to_vec(),
+            500_000_000u64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let accounts = vec![
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(maker_ata_a, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ];
+
+        // Leaving out the config is short of the required accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts[..accounts.len() - 1].to_vec(),
+            data: data.clone(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        );
+
+        // Two trailing accounts are neither a payer nor a whole basket entry
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: [
+                accounts,
+                vec![
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                    AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                ],
+            ]
+            .concat(),
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::TooManyAccountKeys as u32)
+            )
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+    }
+
+    #[test]
+    fn make_with_trailing_instruction_data() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            vec![0u8],
+        ]
+        .concat();
+
+        assert_eq!(data.len(), 2 + MakeInstructionData::LEN + 1);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_non_canonical_bump() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let (_, canonical_bump) = Pubkey::find_program_address(
+            &[ESCROW_SEED, maker.pubkey().as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        );
+
+        // A lower bump that still derives a valid off-curve address for the same maker and seed
+        let escrow_pda = (0..canonical_bump)
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(
+                    &[
+                        ESCROW_SEED,
+                        maker.pubkey().as_ref(),
+                        &seed.to_le_bytes(),
+                        &[bump],
+                    ],
+                    &PROGRAM_ID,
+                )
+                .ok()
+            })
+            .unwrap();
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![MakeInstructionData::VERSION_2],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[ix],
+            EscrowError::InvalidEscrowPda.into(),
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+    }
+
+    #[test]
+    fn make_with_truncated_instruction_data() {
+        let mut data = [0u8; 1 + MakeInstructionData::LEN];
+        data[0] = MakeInstructionData::VERSION_2;
+
+        for len in [0, 1, 9, 25, MakeInstructionData::LEN] {
+            assert_eq!(
+                MakeInstructionData::try_from(&data[..len]).err(),
+                Some(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn make_instruction_data_versions() {
+        let fields = [
+            42u64.to_le_bytes().to_vec(),
+            100_000_000u64.to_le_bytes().to_vec(),
+            500_000_000u64.to_le_bytes().to_vec(),
+        ]
+        .concat();
+
+        // Version 1 only carries the seed, receive and amount
+        let v1 = MakeInstructionData::try_from(
+            &[vec![MakeInstructionData::VERSION_1], fields.clone()].concat()[..],
+        )
+        .unwrap();
+
+        assert_eq!(v1.seed, 42);
+        assert_eq!(v1.receive, 100_000_000);
+        assert_eq!(v1.amount, 500_000_000);
+        assert_eq!(v1.expiry, 0);
+        assert_eq!(v1.vesting_duration, 0);
+        assert_eq!(v1.authorized_taker, [0u8; 32]);
+        assert_eq!(v1.memo, [0u8; 32]);
+        assert_eq!(v1.basket_len, 0);
+
+        let v2 = MakeInstructionData::try_from(
+            &[
+                vec![MakeInstructionData::VERSION_2],
+                fields.clone(),
+                1_000i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [4u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [6u8; 32].to_vec(),
+                250_000_000u64.to_le_bytes().to_vec(),
+            ]
+            .concat()[..],
+        )
+        .unwrap();
+
+        assert_eq!(v2.seed, 42);
+        assert_eq!(v2.receive, 100_000_000);
+        assert_eq!(v2.amount, 500_000_000);
+        assert_eq!(v2.expiry, 1_000);
+        assert_eq!(v2.authorized_taker, [4u8; 32]);
+        assert_eq!(v2.memo, [6u8; 32]);
+        assert_eq!(v2.basket_len, 1);
+        assert_eq!(v2.basket_amounts[0], 250_000_000);
+
+        // Version 1 has no trailing fields and unknown versions are rejected
+        for data in [
+            [
+                vec![MakeInstructionData::VERSION_1],
+                fields.clone(),
+                vec![0u8],
+            ]
+            .concat(),
+            [vec![3u8], fields].concat(),
+        ] {
+            assert_eq!(
+                MakeInstructionData::try_from(&data[..]).err(),
+                Some(ProgramError::InvalidInstructionData)
+            );
+        }
+    }
+
+    #[test]
+    fn make_with_zero_receive() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 0;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::ZeroAmount as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_fake_token_program() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let fake_token_program = Pubkey::new_unique();
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(fake_token_program, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_same_mint() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::SameMint as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_permanent_delegate_mint() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint_with_permanent_delegate(
+            litesvm,
+            MINT_DECIMALS,
+            1_000_000_000,
+            Pubkey::new_unique(),
+        );
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::UnsupportedMintExtension as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_frozen_default_account_state_mint() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        // Every account of this mint, the vault included, would start out frozen
+        let mint_a = init_mint_with_default_account_state(
+            litesvm,
+            MINT_DECIMALS,
+            1_000_000_000,
+            AccountState::Frozen,
+        );
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::UnsupportedMintExtension as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_allowed_mint_extension() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a =
+            init_mint_with_transfer_fee(litesvm, MINT_DECIMALS, 1_000_000_000, 100, u64::MAX);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+
+        // The returned amount is what the vault holds after the 1% transfer fee
+        let return_data = result.unwrap().return_data;
+
+        assert_eq!(return_data.program_id, PROGRAM_ID);
+
+        let arrived = u64::from_le_bytes(return_data.data.try_into().unwrap());
+        let vault_amount = fetch_account::<TokenAccount>(litesvm, &vault).amount;
+
+        assert_eq!(arrived, vault_amount);
+        assert_eq!(arrived, give_amount - give_amount / 100);
+    }
+
+    #[test]
+    fn make_with_wrong_mint_maker_ata_a() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Place a funded token account for another mint at the maker's ATA A address
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 1_000_000_000);
+        let maker_ata_c_acc = litesvm.get_account(&maker_ata_c).unwrap();
+        litesvm.set_account(maker_ata_a, maker_ata_c_acc).unwrap();
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_wrong_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let wrong_owner = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let vault = get_associated_token_address_with_program_id(
+            &wrong_owner.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let data = [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_err());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn make_with_native_sol() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, 10 * LAMPORTS_PER_SOL);
+        let mint_a = init_native_mint(litesvm);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = LAMPORTS_PER_SOL;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let vault_acc = fetch_account::<TokenAccount>(litesvm, &vault);
+
+        assert!(vault_acc.is_native());
+        assert_eq!(vault_acc.amount, give_amount);
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            0
+        );
     }
 }
@@ -0,0 +1,243 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{EscrowError, Make, Take, TakeAccounts, TakeInstructionData};
+
+pub struct MakeAndTake<'a> {
+    pub make: Make<'a>,
+    pub take_accounts: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for MakeAndTake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // The 10 Make accounts come first, without a separate payer or basket, followed by the
+        // Take accounts
+        let (make_accounts, take_accounts) = accounts
+            .split_at_checked(10)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let make = Make::try_from((data, make_accounts))?;
+
+        Ok(Self {
+            make,
+            take_accounts,
+        })
+    }
+}
+
+impl<'a> MakeAndTake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+
+    // Opens the escrow and fills it in one go, so the escrow and vault are closed again before
+    // the instruction ends
+    pub fn process(&mut self) -> ProgramResult {
+        self.make.process()?;
+
+        // The escrow only exists once Make has run, so Take is loaded afterwards
+        let accounts = TakeAccounts::try_from(self.take_accounts)?;
+
+        // Check if Take fills the escrow that was just opened
+        if accounts.escrow.key().ne(self.make.accounts.escrow.key()) {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // An empty Take payload fills the whole offer
+        Take::new(accounts, TakeInstructionData::try_from(&[] as &[u8])?)?.process()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        EscrowError, FEE_BPS, split_fee,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn make_and_take() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let make_accounts = vec![
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(maker_ata_a, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ];
+        let take_accounts = vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(taker_ata_a, false),
+            AccountMeta::new(taker_ata_b, false),
+            AccountMeta::new(maker_ata_b, false),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(treasury_ata_b, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ];
+        let data = [
+            vec![25u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat();
+
+        // Take must fill the escrow opened by Make, not another escrow of the same maker
+        let other_escrow_pda = get_escrow_pda(&maker.pubkey(), seed + 1);
+        let other_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(other_escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(
+                    get_vault_address(&other_escrow_pda, &mint_a, &TOKEN_PROGRAM_ID),
+                    false,
+                ),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![1u8],
+                (seed + 1).to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[other_ix]);
+
+        assert!(result.is_ok());
+
+        let mut other_take_accounts = take_accounts.clone();
+        other_take_accounts[2] = AccountMeta::new(other_escrow_pda, false);
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker, &taker],
+            &taker.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: [make_accounts.clone(), other_take_accounts].concat(),
+                data: data.clone(),
+            }],
+            EscrowError::InvalidEscrowPda.into(),
+        );
+
+        let pre_maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+        let pre_maker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: [make_accounts, take_accounts].concat(),
+            data,
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker, &taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // Neither the escrow nor the vault outlives the instruction
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert!(litesvm.get_account(&vault).is_none());
+
+        // The rent paid by the maker is refunded within the same instruction
+        assert_eq!(
+            litesvm.get_account(&maker.pubkey()).unwrap().lamports,
+            pre_maker_lamports
+        );
+
+        let (fee, maker_receive) = split_fee(receive_amount, FEE_BPS).unwrap();
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            pre_maker_ata_a_bal - give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            maker_receive
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &treasury_ata_b).amount,
+            fee
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_b).amount,
+            1_000_000_000 - receive_amount
+        );
+    }
+}
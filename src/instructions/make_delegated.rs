@@ -0,0 +1,163 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{EscrowError, Make, TokenAccountInterface};
+
+pub struct MakeDelegated<'a> {
+    pub make: Make<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for MakeDelegated<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let mut make = Make::try_from((data, accounts))?;
+
+        // Basket deposits are still transferred by the maker, so only mint A can be delegated
+        if !make.accounts.basket.is_empty() {
+            return Err(EscrowError::BasketMismatch.into());
+        }
+
+        // Check if the maker approved the escrow to move at least the deposit
+        if TokenAccountInterface::delegate(make.accounts.maker_ata_a)?
+            .ne(&Some(*make.accounts.escrow.key()))
+        {
+            return Err(EscrowError::InvalidDelegate.into());
+        }
+
+        if TokenAccountInterface::delegated_amount(make.accounts.maker_ata_a)?
+            < make.instruction_data.amount
+        {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        make.delegated = true;
+
+        Ok(Self { make })
+    }
+}
+
+impl<'a> MakeDelegated<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    // Same accounts and data as Make, with the escrow PDA approved as delegate of the maker ATA
+    pub fn process(&mut self) -> ProgramResult {
+        self.make.process()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
+    use solana_signer::Signer;
+    use solana_transaction_error::TransactionError;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+    use spl_token_2022::{instruction::approve, state::Account as TokenAccount};
+
+    use crate::{
+        Escrow, EscrowError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+            },
+        },
+    };
+
+    #[test]
+    fn make_delegated() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![12u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        // The escrow hasn't been approved as delegate yet
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            core::slice::from_ref(&ix),
+        );
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::InvalidDelegate as u32)
+            )
+        );
+
+        let approve_ix = approve(
+            &TOKEN_PROGRAM_ID,
+            &maker_ata_a,
+            &escrow_pda,
+            &maker.pubkey(),
+            &[],
+            give_amount,
+        )
+        .unwrap();
+
+        let result =
+            build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[approve_ix, ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.maker, maker.pubkey().to_bytes());
+        assert_eq!(escrow.receive, receive_amount);
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+
+        let maker_ata_a_acc = fetch_account::<TokenAccount>(litesvm, &maker_ata_a);
+
+        assert_eq!(maker_ata_a_acc.amount, 1_000_000_000 - give_amount);
+        assert_eq!(maker_ata_a_acc.delegated_amount, 0);
+    }
+}
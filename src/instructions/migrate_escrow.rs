@@ -0,0 +1,433 @@
+use core::mem::offset_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    AccountCheck, ESCROW_SEED_PREFIX, Escrow, EscrowError, FEE_BPS, MintInterface, NATIVE_MINT,
+    SignerAccount, SystemProgram, read_bytes,
+};
+
+pub struct MigrateEscrowAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateEscrowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow, mint_a, mint_b, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        SignerAccount::check(maker)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+
+        // The escrow still has an older size, so ProgramAccount::check would reject it
+        if escrow.owner().ne(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // Return the accounts
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            system_program,
+        })
+    }
+}
+
+pub struct MigrateEscrow<'a> {
+    pub accounts: MigrateEscrowAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateEscrow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateEscrowAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> MigrateEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&self) -> ProgramResult {
+        match self.accounts.escrow.data_len() {
+            Escrow::LEGACY_LEN => self.migrate_legacy()?,
+            Escrow::V1_LEN => self.migrate_v1()?,
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+
+        // Log the escrow
+        sol_log("Instruction: MigrateEscrow");
+        pubkey::log(self.accounts.escrow.key());
+
+        Ok(())
+    }
+
+    fn migrate_legacy(&self) -> ProgramResult {
+        // Read the legacy fields before the account is resized over them
+        let (seed, maker, mint_a, mint_b, receive, bump) = {
+            let data = self.accounts.escrow.try_borrow_data()?;
+
+            (
+                u64::from_le_bytes(read_bytes(&data, 0)?),
+                read_bytes::<32>(&data, 8)?,
+                read_bytes::<32>(&data, 40)?,
+                read_bytes::<32>(&data, 72)?,
+                u64::from_le_bytes(read_bytes(&data, 104)?),
+                read_bytes::<1>(&data, 112)?,
+            )
+        };
+
+        // Check if the maker signed for the escrow
+        if maker.ne(self.accounts.maker.key()) {
+            return Err(EscrowError::MakerMismatch.into());
+        }
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[ESCROW_SEED_PREFIX, &maker, &seed.to_le_bytes(), &bump],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Check if the mints match the escrow
+        if mint_a.ne(self.accounts.mint_a.key()) || mint_b.ne(self.accounts.mint_b.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+
+        self.resize()?;
+
+        // Fields the legacy layout didn't have take the defaults Make would have written, and
+        // the fee rate InitConfig starts with
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        Escrow::load_mut_uninitialized(data.as_mut())?.set_inner(
+            seed,
+            maker,
+            mint_a,
+            mint_b,
+            receive,
+            0,
+            0,
+            0,
+            0,
+            [0u8; 32],
+            maker,
+            [0u8; 32],
+            MintInterface::decimals(self.accounts.mint_a)?,
+            MintInterface::decimals(self.accounts.mint_b)?,
+            mint_b.eq(&NATIVE_MINT),
+            bump,
+            FEE_BPS,
+        );
+
+        Ok(())
+    }
+
+    fn migrate_v1(&self) -> ProgramResult {
+        // Version 1 escrows hold every field but the fee rate, at the same offsets
+        let (maker, mint_a, mint_b) = {
+            let data = self.accounts.escrow.try_borrow_data()?;
+            if data[..8] != Escrow::V1_DISCRIMINATOR {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            (
+                read_bytes::<32>(&data, offset_of!(Escrow, maker))?,
+                read_bytes::<32>(&data, offset_of!(Escrow, mint_a))?,
+                read_bytes::<32>(&data, offset_of!(Escrow, mint_b))?,
+            )
+        };
+
+        // Check if the maker signed for the escrow
+        if maker.ne(self.accounts.maker.key()) {
+            return Err(EscrowError::MakerMismatch.into());
+        }
+
+        // Check if the mints match the escrow
+        if mint_a.ne(self.accounts.mint_a.key()) || mint_b.ne(self.accounts.mint_b.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+
+        self.resize()?;
+
+        // The rate these escrows were opened at isn't stored, so they get the one InitConfig
+        // starts with
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&Escrow::DISCRIMINATOR);
+        Escrow::load_mut(data.as_mut())?.set_fee_bps(FEE_BPS);
+
+        Ok(())
+    }
+
+    // Top up the rent for the larger account from the maker, then grow it to the current layout
+    fn resize(&self) -> ProgramResult {
+        let rent = Rent::get()?.minimum_balance(Escrow::LEN);
+        let shortfall = rent.saturating_sub(self.accounts.escrow.lamports());
+        if shortfall > 0 {
+            Transfer {
+                from: self.accounts.maker,
+                to: self.accounts.escrow,
+                lamports: shortfall,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.escrow.resize(Escrow::LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_account::Account;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Escrow, EscrowError, FEE_BPS,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, ESCROW_SEED, MINT_DECIMALS, PROGRAM_ID,
+                SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn migrate_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let other = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, 9, 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let bump = Pubkey::find_program_address(
+            &[ESCROW_SEED, maker.pubkey().as_ref(), &seed.to_le_bytes()],
+            &PROGRAM_ID,
+        )
+        .1;
+
+        // An escrow in the legacy layout: seed, maker, mint A, mint B, receive and bump
+        let data = [
+            seed.to_le_bytes().to_vec(),
+            maker.pubkey().to_bytes().to_vec(),
+            mint_a.to_bytes().to_vec(),
+            mint_b.to_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            vec![bump],
+        ]
+        .concat();
+        assert_eq!(data.len(), Escrow::LEGACY_LEN);
+
+        litesvm
+            .set_account(
+                escrow_pda,
+                Account {
+                    lamports: litesvm.minimum_balance_for_rent_exemption(data.len()),
+                    data,
+                    owner: PROGRAM_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+
+        let migrate_ix = |signer: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+            data: vec![19u8],
+        };
+
+        // Only the maker can migrate the escrow
+        simulate_expect_error(
+            litesvm,
+            &[&other],
+            &other.pubkey(),
+            &[migrate_ix(other.pubkey())],
+            EscrowError::MakerMismatch.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[migrate_ix(maker.pubkey())],
+        );
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+
+        assert_eq!(escrow_acc.data.len(), Escrow::LEN);
+        assert_eq!(
+            escrow_acc.lamports,
+            litesvm.minimum_balance_for_rent_exemption(Escrow::LEN)
+        );
+
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.discriminator, Escrow::DISCRIMINATOR);
+        assert_eq!(escrow.seed, seed);
+        assert_eq!(escrow.maker, maker.pubkey().to_bytes());
+        assert_eq!(escrow.creator, maker.pubkey().to_bytes());
+        assert_eq!(escrow.mint_a, mint_a.to_bytes());
+        assert_eq!(escrow.mint_b, mint_b.to_bytes());
+        assert_eq!(escrow.receive, receive_amount);
+        assert_eq!(escrow.expiry, 0);
+        assert_eq!(escrow.vault_rent_recipient, maker.pubkey().to_bytes());
+        assert_eq!(escrow.mint_a_decimals, MINT_DECIMALS);
+        assert_eq!(escrow.mint_b_decimals, 9);
+        assert!(!escrow.receive_native);
+        assert_eq!(escrow.bump(), bump);
+
+        // A migrated escrow no longer has the legacy size
+        litesvm.expire_blockhash();
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[migrate_ix(maker.pubkey())],
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    #[test]
+    fn migrate_escrow_v1() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let other = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, 9, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![1u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // An escrow in the version 1 layout, which ends at the bump
+        let mut escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        escrow_acc.data.truncate(Escrow::V1_LEN);
+        escrow_acc.data[..8].copy_from_slice(b"escrowv1");
+        escrow_acc.lamports = litesvm.minimum_balance_for_rent_exemption(Escrow::V1_LEN);
+        let v1_data = escrow_acc.data.clone();
+        litesvm.set_account(escrow_pda, escrow_acc).unwrap();
+
+        let migrate_ix = |signer: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+            data: vec![19u8],
+        };
+
+        // Only the maker can migrate the escrow
+        simulate_expect_error(
+            litesvm,
+            &[&other],
+            &other.pubkey(),
+            &[migrate_ix(other.pubkey())],
+            EscrowError::MakerMismatch.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[migrate_ix(maker.pubkey())],
+        );
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+
+        assert_eq!(escrow_acc.data.len(), Escrow::LEN);
+        assert_eq!(
+            escrow_acc.lamports,
+            litesvm.minimum_balance_for_rent_exemption(Escrow::LEN)
+        );
+
+        // Every version 1 field is kept in place
+        assert_eq!(escrow_acc.data[8..Escrow::V1_LEN], v1_data[8..]);
+
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.discriminator, Escrow::DISCRIMINATOR);
+        assert_eq!(escrow.maker, maker.pubkey().to_bytes());
+        assert_eq!(escrow.receive, receive_amount);
+        assert_eq!(escrow.fee_bps(), FEE_BPS);
+    }
+}
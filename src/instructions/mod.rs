@@ -1,9 +1,57 @@
+pub mod amend;
+pub mod claim;
+pub mod close_escrow;
+pub mod commit_take;
+pub mod extend_expiry;
 pub mod helpers;
+pub mod init_config;
 pub mod make;
+pub mod make_and_take;
+pub mod make_delegated;
+pub mod migrate_escrow;
+pub mod query_escrow;
+pub mod reap;
 pub mod refund;
+pub mod reveal_take;
+pub mod set_fee;
+pub mod set_fee_recipient;
+pub mod set_min_deposit;
+pub mod set_paused;
+pub mod set_receive_options;
+pub mod split;
 pub mod take;
+pub mod take_many;
+pub mod take_native;
+pub mod take_to;
+pub mod top_up;
+pub mod transfer_maker;
+pub mod update_receive;
 
+pub use amend::*;
+pub use claim::*;
+pub use close_escrow::*;
+pub use commit_take::*;
+pub use extend_expiry::*;
 pub use helpers::*;
+pub use init_config::*;
 pub use make::*;
+pub use make_and_take::*;
+pub use make_delegated::*;
+pub use migrate_escrow::*;
+pub use query_escrow::*;
+pub use reap::*;
 pub use refund::*;
+pub use reveal_take::*;
+pub use set_fee::*;
+pub use set_fee_recipient::*;
+pub use set_min_deposit::*;
+pub use set_paused::*;
+pub use set_receive_options::*;
+pub use split::*;
 pub use take::*;
+pub use take_many::*;
+pub use take_native::*;
+pub use take_to::*;
+pub use top_up::*;
+pub use transfer_maker::*;
+pub use update_receive::*;
@@ -0,0 +1,217 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::{Pubkey, create_program_address},
+};
+
+use crate::{
+    AccountCheck, ESCROW_SEED_PREFIX, Escrow, EscrowError, ProgramAccount, TokenAccountInterface,
+};
+
+pub struct QueryEscrowAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for QueryEscrowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow, vault] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        ProgramAccount::check(escrow)?;
+        TokenAccountInterface::check(vault)?;
+
+        // Return the accounts
+        Ok(Self {
+            maker,
+            escrow,
+            vault,
+        })
+    }
+}
+
+pub struct QueryEscrow<'a> {
+    pub accounts: QueryEscrowAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for QueryEscrow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = QueryEscrowAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> QueryEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    // Seed, maker, mint A, mint B, receive, vault balance and memo
+    pub const RETURN_DATA_LEN: usize = size_of::<u64>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<u64>()
+        + size_of::<[u8; 32]>();
+
+    pub fn process(&self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow_data()?;
+        let escrow = Escrow::load(&data)?;
+
+        // Check if the maker matches the escrow
+        if escrow.maker.ne(self.accounts.maker.key()) {
+            return Err(EscrowError::MakerMismatch.into());
+        }
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Check if the vault belongs to the escrow
+        if TokenAccountInterface::owner(self.accounts.vault)?.ne(self.accounts.escrow.key())
+            || TokenAccountInterface::mint(self.accounts.vault)?.ne(&escrow.mint_a)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_amount = TokenAccountInterface::amount(self.accounts.vault)?;
+
+        let mut return_data = [0u8; Self::RETURN_DATA_LEN];
+        return_data[0..8].copy_from_slice(&escrow.seed.to_le_bytes());
+        return_data[8..40].copy_from_slice(&escrow.maker);
+        return_data[40..72].copy_from_slice(&escrow.mint_a);
+        return_data[72..104].copy_from_slice(&escrow.mint_b);
+        return_data[104..112].copy_from_slice(&escrow.receive.to_le_bytes());
+        return_data[112..120].copy_from_slice(&vault_amount.to_le_bytes());
+        return_data[120..152].copy_from_slice(&escrow.memo);
+
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        QueryEscrow,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, build_and_simulate_transaction, init_ata, init_mint,
+                init_wallet, setup,
+            },
+        },
+    };
+
+    #[test]
+    fn query_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(maker.pubkey(), false),
+                AccountMeta::new_readonly(escrow_pda, false),
+                AccountMeta::new_readonly(vault, false),
+            ],
+            data: [vec![6u8]].concat(),
+        };
+
+        let result = build_and_simulate_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let return_data = result.unwrap().meta.return_data;
+
+        assert_eq!(return_data.program_id, PROGRAM_ID);
+        assert_eq!(return_data.data.len(), QueryEscrow::RETURN_DATA_LEN);
+
+        let data = return_data.data;
+
+        assert_eq!(u64::from_le_bytes(data[0..8].try_into().unwrap()), seed);
+        assert_eq!(Pubkey::try_from(&data[8..40]).unwrap(), maker.pubkey());
+        assert_eq!(Pubkey::try_from(&data[40..72]).unwrap(), mint_a);
+        assert_eq!(Pubkey::try_from(&data[72..104]).unwrap(), mint_b);
+        assert_eq!(
+            u64::from_le_bytes(data[104..112].try_into().unwrap()),
+            receive_amount
+        );
+        assert_eq!(
+            u64::from_le_bytes(data[112..120].try_into().unwrap()),
+            give_amount
+        );
+        assert_eq!(data[120..152], [0u8; 32]);
+    }
+}
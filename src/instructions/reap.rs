@@ -0,0 +1,308 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    AccountCheck, AssociatedTokenProgram, EscrowError, MintInterface, ProgramAccount, Refund,
+    RefundAccounts, SignerAccount, SystemProgram, TokenProgramInterface,
+};
+
+pub struct Reap<'a> {
+    pub refund: Refund<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Reap<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        // The reaper comes first, followed by the Refund accounts with the maker no longer signing
+        let [
+            reaper,
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            maker_ata_a,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            basket @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // A mint, vault and maker ATA follow for each basket entry
+        if basket.len() % 3 != 0 {
+            return Err(EscrowError::TooManyAccountKeys.into());
+        }
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        SignerAccount::check(reaper)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+
+        let accounts = RefundAccounts {
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            // Only the maker can send the refund elsewhere
+            recipient: maker,
+            maker_ata_a,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            payer: reaper,
+            basket,
+        };
+
+        let mut refund = Refund::new(accounts)?;
+        refund.reap = true;
+
+        Ok(Self { refund })
+    }
+}
+
+impl<'a> Reap<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    // Anyone can return the deposit to the maker once the escrow has been expired for
+    // REAP_GRACE_PERIOD, and is paid REAP_BOUNTY_BPS of the reclaimed rent for it
+    pub fn process(&self) -> ProgramResult {
+        self.refund.process()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_clock::Clock;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        EscrowError, REAP_BOUNTY_BPS, REAP_GRACE_PERIOD, split_fee,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn reap() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let reaper = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let expiry = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                expiry.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(reaper.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![16u8],
+        };
+
+        // An expired escrow is still left to its maker during the grace period
+        clock.unix_timestamp = expiry + REAP_GRACE_PERIOD;
+        litesvm.set_sysvar(&clock);
+
+        simulate_expect_error(
+            litesvm,
+            &[default_payer, &reaper],
+            &default_payer.pubkey(),
+            core::slice::from_ref(&ix),
+            EscrowError::NotReapable.into(),
+        );
+
+        clock.unix_timestamp = expiry + REAP_GRACE_PERIOD + 1;
+        litesvm.set_sysvar(&clock);
+
+        let maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+        let reaper_lamports = litesvm.get_account(&reaper.pubkey()).unwrap().lamports;
+        let reclaimed_lamports = litesvm.get_account(&escrow_pda).unwrap().lamports
+            + litesvm.get_account(&vault).unwrap().lamports;
+
+        // The default payer covers the transaction fee so the reaper only gains the bounty
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer, &reaper],
+            &default_payer.pubkey(),
+            &[ix],
+        );
+
+        assert!(result.is_ok());
+
+        let (bounty, _) = split_fee(reclaimed_lamports, REAP_BOUNTY_BPS).unwrap();
+
+        assert!(bounty > 0);
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert!(litesvm.get_account(&vault).is_none());
+        assert_eq!(
+            litesvm.get_account(&reaper.pubkey()).unwrap().lamports,
+            reaper_lamports + bounty
+        );
+        assert_eq!(
+            litesvm.get_account(&maker.pubkey()).unwrap().lamports,
+            maker_lamports + reclaimed_lamports - bounty
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn reap_with_spoofed_vault() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let reaper = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let expiry = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                expiry.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        clock.unix_timestamp = expiry + REAP_GRACE_PERIOD + 1;
+        litesvm.set_sysvar(&clock);
+
+        // An empty escrow-owned ATA for a junk mint stands in for the real vault
+        let junk_mint = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let junk_vault = init_ata(litesvm, junk_mint, escrow_pda, 0);
+        let maker_junk_ata = init_ata(litesvm, junk_mint, maker.pubkey(), 0);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(reaper.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(junk_mint, false),
+                AccountMeta::new(junk_vault, false),
+                AccountMeta::new(maker_junk_ata, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![16u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[default_payer, &reaper],
+            &default_payer.pubkey(),
+            &[ix],
+            EscrowError::MintMismatch.into(),
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+}
@@ -1,18 +1,21 @@
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
+    cpi::set_return_data,
     instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64},
     program_error::ProgramError,
-    pubkey::create_program_address,
-};
-use pinocchio_token::{
-    instructions::{CloseAccount, Transfer},
-    state::TokenAccount,
+    pubkey::{self, create_program_address, find_program_address},
+    sysvars::{Sysvar, clock::Clock},
 };
+use pinocchio_token_2022::instructions::{CloseAccount, TransferChecked};
 
 use crate::{
-    AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountInit, Escrow,
-    MintInterface, ProgramAccount, SignerAccount,
+    AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountCheck,
+    AssociatedTokenAccountInit, AssociatedTokenProgram, ESCROW_SEED_PREFIX, Escrow, EscrowError,
+    HarvestWithheldTokensToMint, MintInterface, PinocchioError, ProgramAccount, REAP_BOUNTY_BPS,
+    REAP_GRACE_PERIOD, SignerAccount, SystemProgram, TRANSFER_FEE_CONFIG_EXTENSION,
+    TokenAccountInterface, TokenProgramInterface, account_groups, assert_is_maker, split_fee,
 };
 
 pub struct RefundAccounts<'a> {
@@ -20,10 +23,15 @@ pub struct RefundAccounts<'a> {
     pub escrow: &'a AccountInfo,
     pub mint_a: &'a AccountInfo,
     pub vault: &'a AccountInfo,
+    // Owner of maker_ata_a and the basket ATAs, the maker unless Refund names another wallet
+    pub recipient: &'a AccountInfo,
     pub maker_ata_a: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub associated_token_account_program: &'a AccountInfo,
+    // Funds the maker ATAs and collects the bounty, the maker unless Reap names the caller
+    pub payer: &'a AccountInfo,
+    pub basket: &'a [AccountInfo],
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
@@ -39,14 +47,29 @@ impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
             system_program,
             token_program,
             associated_token_account_program,
+            remaining_accounts @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        // An optional recipient comes first, then a mint, vault and recipient ATA for each
+        // basket entry
+        let (recipient, basket) = match (remaining_accounts.len() % 3, remaining_accounts) {
+            (0, basket) => (maker, basket),
+            (1, [recipient, basket @ ..]) => (recipient, basket),
+            _ => return Err(EscrowError::TooManyAccountKeys.into()),
+        };
+
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
         SignerAccount::check(maker)?;
-        ProgramAccount::check(escrow)?;
+        // A retried Refund finds the escrow already closed, which process treats as a no-op
+        if !ProgramAccount::is_closed(escrow) {
+            ProgramAccount::check(escrow)?;
+        }
         MintInterface::check(mint_a)?;
 
         // Return the accounts
@@ -54,17 +77,22 @@ impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
             maker,
             escrow,
             mint_a,
-            maker_ata_a,
             vault,
+            recipient,
+            maker_ata_a,
             system_program,
             token_program,
             associated_token_account_program,
+            payer: maker,
+            basket,
         })
     }
 }
 
 pub struct Refund<'a> {
     pub accounts: RefundAccounts<'a>,
+    // Set by Reap, which lets anyone close a long expired escrow for a bounty
+    pub reap: bool,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for Refund<'a> {
@@ -73,99 +101,308 @@ impl<'a> TryFrom<&'a [AccountInfo]> for Refund<'a> {
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let accounts = RefundAccounts::try_from(accounts)?;
 
+        Refund::new(accounts)
+    }
+}
+
+impl<'a> Refund<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn new(accounts: RefundAccounts<'a>) -> Result<Self, ProgramError> {
+        // Nothing is returned from a closed escrow, so don't create ATAs for it
+        if ProgramAccount::is_closed(accounts.escrow) {
+            return Ok(Self {
+                accounts,
+                reap: false,
+            });
+        }
+
+        // An existing account must be the recipient's ATA for mint A
         AssociatedTokenAccount::init_if_needed(
             accounts.maker_ata_a,
             accounts.mint_a,
-            accounts.maker,
-            accounts.maker,
+            accounts.payer,
+            accounts.recipient,
             accounts.system_program,
             accounts.token_program,
         )?;
 
-        Ok(Self { accounts })
-    }
-}
+        for [mint, _, maker_ata] in account_groups::<3>(accounts.basket) {
+            AssociatedTokenAccount::init_if_needed(
+                maker_ata,
+                mint,
+                accounts.payer,
+                accounts.recipient,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
-impl<'a> Refund<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &2;
+        Ok(Self {
+            accounts,
+            reap: false,
+        })
+    }
 
     pub fn process(&self) -> ProgramResult {
+        // The escrow was already refunded or taken, so there is nothing left to do
+        if ProgramAccount::is_closed(self.accounts.escrow) {
+            self.check_vault_closed()?;
+
+            sol_log("Instruction: Refund (escrow already closed)");
+            pubkey::log(self.accounts.escrow.key());
+
+            return Ok(());
+        }
+
         let data = self.accounts.escrow.try_borrow_data()?;
         let escrow = Escrow::load(&data)?;
 
+        if self.reap {
+            // Check if the tokens are returned to the maker of the escrow
+            if escrow.maker.ne(self.accounts.maker.key()) {
+                return Err(EscrowError::MakerMismatch.into());
+            }
+
+            // Check if the escrow expired more than the grace period ago
+            let now = Clock::get()?.unix_timestamp;
+            if escrow.expiry == 0 || now <= escrow.expiry.saturating_add(REAP_GRACE_PERIOD) {
+                return Err(EscrowError::NotReapable.into());
+            }
+        } else {
+            // Check if the maker signed for the escrow
+            assert_is_maker(escrow, self.accounts.maker)?;
+        }
+
+        // Vesting escrows are locked until fully claimed
+        if escrow.vesting_duration != 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
+
         // Check if the escrow is valid
         let escrow_key = create_program_address(
             &[
-                b"escrow",
-                self.accounts.maker.key(),
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
                 &escrow.seed.to_le_bytes(),
                 &escrow.bump,
             ],
             &crate::ID,
         )?;
         if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Check if mint A and the vault are the escrow's, so the real vault can't be left behind
+        if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+        AssociatedTokenAccount::check(
+            self.accounts.vault,
+            self.accounts.escrow,
+            self.accounts.mint_a,
+            self.accounts.token_program,
+        )?;
+
+        // Check if there is a mint and vault for each basket entry
+        if self.accounts.basket.len() != escrow.basket().len() * 3 {
+            return Err(EscrowError::BasketMismatch.into());
+        }
+        for (entry, [mint, vault, _]) in escrow
+            .basket()
+            .iter()
+            .zip(account_groups::<3>(self.accounts.basket))
+        {
+            if entry.mint.ne(mint.key()) {
+                return Err(EscrowError::BasketMismatch.into());
+            }
+            AssociatedTokenAccount::check(
+                vault,
+                self.accounts.escrow,
+                mint,
+                self.accounts.token_program,
+            )?;
         }
 
+        let creator_binding = escrow.creator;
         let seed_binding = escrow.seed.to_le_bytes();
         let bump_binding = escrow.bump;
         let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(self.accounts.maker.key().as_ref()),
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(&creator_binding),
             Seed::from(&seed_binding),
             Seed::from(&bump_binding),
         ];
         let signer = Signer::from(&escrow_seeds);
 
-        let amount = {
-            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+        // A frozen vault can neither be emptied nor closed
+        if TokenAccountInterface::is_frozen(self.accounts.vault)? {
+            return Err(EscrowError::VaultFrozen.into());
+        }
 
-            vault.amount()
-        };
+        let amount = TokenAccountInterface::amount(self.accounts.vault)?;
 
-        // Transfer from the Vault to the Maker
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.maker_ata_a,
-            authority: self.accounts.escrow,
-            amount,
+        // Transfer from the Vault to the Maker, unless it was emptied by a permanent delegate
+        if amount > 0 {
+            TransferChecked {
+                from: self.accounts.vault,
+                mint: self.accounts.mint_a,
+                to: self.accounts.maker_ata_a,
+                authority: self.accounts.escrow,
+                amount,
+                decimals: MintInterface::decimals(self.accounts.mint_a)?,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
         }
-        .invoke_signed(&[signer.clone()])?;
+
+        // Harvest fees withheld in the Vault, which would otherwise block closing it
+        if MintInterface::has_extension(self.accounts.mint_a, TRANSFER_FEE_CONFIG_EXTENSION)? {
+            HarvestWithheldTokensToMint {
+                mint: self.accounts.mint_a,
+                source: self.accounts.vault,
+            }
+            .invoke()?;
+        }
+
+        // Track the rent reclaimed from closing accounts
+        let mut reclaimed_lamports = self.accounts.vault.lamports();
 
         // Close the Vault
         CloseAccount {
             account: self.accounts.vault,
             destination: self.accounts.maker,
             authority: self.accounts.escrow,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(core::slice::from_ref(&signer))?;
+
+        // Return each basket token to the Maker and close its Vault
+        for [mint, vault, maker_ata] in account_groups::<3>(self.accounts.basket) {
+            let basket_amount = TokenAccountInterface::amount(vault)?;
+            if basket_amount > 0 {
+                TransferChecked {
+                    from: vault,
+                    mint,
+                    to: maker_ata,
+                    authority: self.accounts.escrow,
+                    amount: basket_amount,
+                    decimals: MintInterface::decimals(mint)?,
+                    token_program: self.accounts.token_program.key(),
+                }
+                .invoke_signed(core::slice::from_ref(&signer))?;
+            }
+
+            if MintInterface::has_extension(mint, TRANSFER_FEE_CONFIG_EXTENSION)? {
+                HarvestWithheldTokensToMint {
+                    mint,
+                    source: vault,
+                }
+                .invoke()?;
+            }
+
+            reclaimed_lamports += vault.lamports();
+
+            CloseAccount {
+                account: vault,
+                destination: self.accounts.maker,
+                authority: self.accounts.escrow,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
         }
-        .invoke_signed(&[signer.clone()])?;
 
         // Close the Escrow
         drop(data);
+        reclaimed_lamports += self.accounts.escrow.lamports();
+
+        // Pay the reaper a share of the reclaimed rent out of the Escrow lamports
+        if self.reap {
+            let (bounty, _) = split_fee(reclaimed_lamports, REAP_BOUNTY_BPS)?;
+
+            {
+                let mut escrow_lamports = self.accounts.escrow.try_borrow_mut_lamports()?;
+                *escrow_lamports = escrow_lamports
+                    .checked_sub(bounty)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+            }
+            *self.accounts.payer.try_borrow_mut_lamports()? += bounty;
+
+            reclaimed_lamports -= bounty;
+        }
+
         ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
 
+        // Log the escrow, maker and refunded amount
+        sol_log("Instruction: Refund");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.maker.key());
+        sol_log_64(amount, 0, 0, 0, 0);
+
+        set_return_data(&reclaimed_lamports.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn check_vault_closed(&self) -> ProgramResult {
+        // Any system account with no lamports looks closed, so the escrow's vault for mint A
+        // must be gone as well before the call counts as a retried Refund
+        if self
+            .accounts
+            .mint_a
+            .owner()
+            .ne(self.accounts.token_program.key())
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if find_program_address(
+            &[
+                self.accounts.escrow.key(),
+                self.accounts.token_program.key(),
+                self.accounts.mint_a.key(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        )
+        .0
+        .ne(self.accounts.vault.key())
+        {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        if !self.accounts.vault.data_is_empty() {
+            return Err(EscrowError::VaultNotClosed.into());
+        }
+
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use solana_instruction::{AccountMeta, Instruction};
+    use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
     use solana_signer::Signer;
+    use solana_transaction_error::TransactionError;
     use spl_associated_token_account::{
         get_associated_token_address_with_program_id,
         solana_program::native_token::LAMPORTS_PER_SOL,
     };
     use spl_token_2022::state::Account as TokenAccount;
 
-    use crate::tests::{
-        constants::{
-            ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
-            TOKEN_PROGRAM_ID,
-        },
-        pda::get_escrow_pda,
-        utils::{
-            build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+    use crate::{
+        EscrowError, PinocchioError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, freeze_token_account, init_ata,
+                init_mint, init_wallet, setup, simulate_expect_error,
+            },
         },
     };
 
@@ -182,8 +419,7 @@ mod tests {
         let receive_amount: u64 = 100_000_000;
         let give_amount: u64 = 500_000_000;
         let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
-        let vault =
-            get_associated_token_address_with_program_id(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
 
         let ix = Instruction {
             program_id: PROGRAM_ID,
@@ -197,12 +433,21 @@ mod tests {
                 AccountMeta::new(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
             ],
             data: [
                 vec![0u8],
+                vec![2u8],
                 seed.to_le_bytes().to_vec(),
                 receive_amount.to_le_bytes().to_vec(),
                 give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
             ]
             .concat(),
         };
@@ -240,4 +485,706 @@ mod tests {
 
         assert_eq!(pre_maker_ata_a_bal, post_maker_ata_a_bal - give_amount);
     }
+
+    #[test]
+    fn refund_twice() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            core::slice::from_ref(&ix),
+        );
+
+        assert!(result.is_ok());
+
+        let pre_maker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount;
+
+        // A retried Refund finds the escrow closed and succeeds without doing anything
+        litesvm.expire_blockhash();
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+
+        let post_maker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount;
+
+        assert_eq!(pre_maker_ata_a_bal, post_maker_ata_a_bal);
+    }
+
+    #[test]
+    fn refund_with_unrelated_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let refund_ix = |escrow: Pubkey, vault: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        // An address that never held an escrow looks closed, but the live vault isn't its ATA
+        let unrelated = Keypair::new().pubkey();
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[refund_ix(unrelated, vault)],
+            PinocchioError::InvalidAddress.into(),
+        );
+
+        // Its own ATA for mint A still exists, so there is no refunded escrow behind it either
+        let unrelated_vault = init_ata(litesvm, mint_a, unrelated, 0);
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[refund_ix(unrelated, unrelated_vault)],
+            EscrowError::VaultNotClosed.into(),
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn refund_to_recipient() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let recipient = Keypair::new();
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let recipient_ata_a = get_associated_token_address_with_program_id(
+            &recipient.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let refund_ix = |destination| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(recipient.pubkey(), false),
+            ],
+            data: vec![2u8],
+        };
+
+        // The destination must be the recipient's ATA, not the maker's
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[refund_ix(maker_ata_a)],
+        );
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[refund_ix(recipient_ata_a)],
+        );
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert!(litesvm.get_account(&vault).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &recipient_ata_a).amount,
+            give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            1_000_000_000 - give_amount
+        );
+    }
+
+    #[test]
+    fn refund_with_emptied_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Drain the vault behind the escrow's back
+        init_ata(litesvm, mint_a, escrow_pda, 0);
+
+        let pre_maker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+
+        let vault_acc = litesvm.get_account(&vault);
+
+        assert!(vault_acc.is_none());
+
+        let post_maker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount;
+
+        assert_eq!(pre_maker_ata_a_bal, post_maker_ata_a_bal);
+    }
+
+    #[test]
+    fn refund_with_frozen_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // The mint's freeze authority freezes the vault mid-escrow
+        freeze_token_account(litesvm, vault);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![2u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::VaultFrozen as u32)
+            )
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn refund_basket() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let give_amount_c: u64 = 250_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault_c = get_vault_address(&escrow_pda, &mint_c, &TOKEN_PROGRAM_ID);
+
+        // Mint C, the maker's ATA and the escrow's vault for it follow the fixed accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(maker_ata_c, false),
+                AccountMeta::new(vault_c, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                give_amount_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let pre_maker_ata_c_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_c).amount;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(vault_c, false),
+                AccountMeta::new(maker_ata_c, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+
+        let vault_c_acc = litesvm.get_account(&vault_c);
+
+        assert!(vault_c_acc.is_none());
+
+        let post_maker_ata_c_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_c).amount;
+
+        assert_eq!(post_maker_ata_c_bal, pre_maker_ata_c_bal + give_amount_c);
+    }
+
+    #[test]
+    fn refund_with_invalid_escrow_length() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Truncate the escrow so it no longer matches Escrow::LEN
+        let mut escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        escrow_acc.data.pop();
+        litesvm.set_account(escrow_pda, escrow_acc).unwrap();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn refund_with_surplus_accounts() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                // Neither a recipient nor a whole basket entry
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new_readonly(mint_b, false),
+            ],
+            data: [vec![2u8]].concat(),
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[ix],
+            EscrowError::TooManyAccountKeys.into(),
+        );
+
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
 }
@@ -0,0 +1,365 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    AccountCheck, AccountClose, COMMITMENT_SEED, Commitment, CommitmentAccount, EscrowError,
+    MIN_REVEAL_DELAY_SLOTS, ProgramAccount, Take, TakeAccounts, TakeInstructionData, read_bytes,
+};
+
+pub struct RevealTakeInstructionData {
+    pub nonce: [u8; 32],
+    pub take: TakeInstructionData,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RevealTakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // The nonce comes first, followed by the Take payload
+        let nonce: [u8; 32] = read_bytes(data, 0)?;
+        let take = TakeInstructionData::try_from(&data[nonce.len()..])?;
+
+        Ok(Self { nonce, take })
+    }
+}
+
+pub struct RevealTake<'a> {
+    pub take: Take<'a>,
+    pub commitment: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RevealTake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // The commitment comes first, followed by the Take accounts
+        let [commitment, accounts @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let accounts = TakeAccounts::try_from(accounts)?;
+        let instruction_data = RevealTakeInstructionData::try_from(data)?;
+        CommitmentAccount::check(commitment)?;
+
+        // Check the reveal against the commitment before Take creates any ATAs
+        {
+            let data = commitment.try_borrow_data()?;
+            let state = Commitment::load(&data)?;
+
+            let commitment_key = create_program_address(
+                &[COMMITMENT_SEED, state.escrow(), &state.bump],
+                &crate::ID,
+            )?;
+            if &commitment_key != commitment.key() || state.escrow().ne(accounts.escrow.key()) {
+                return Err(ProgramError::InvalidSeeds);
+            }
+
+            if Clock::get()?.slot < state.slot().saturating_add(MIN_REVEAL_DELAY_SLOTS) {
+                return Err(EscrowError::RevealTooEarly.into());
+            }
+
+            if CommitmentAccount::hash(accounts.taker.key(), &instruction_data.nonce)
+                .ne(state.hash())
+            {
+                return Err(EscrowError::CommitmentMismatch.into());
+            }
+        }
+
+        Ok(Self {
+            take: Take::new(accounts, instruction_data.take)?,
+            commitment,
+        })
+    }
+}
+
+impl<'a> RevealTake<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+
+    // The commitment is spent by the Take, its rent goes back to the taker
+    pub fn process(&mut self) -> ProgramResult {
+        self.take.process()?;
+
+        ProgramAccount::close(self.commitment, self.take.accounts.taker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use litesvm::LiteSVM;
+    use pinocchio::program_error::ProgramError;
+    use solana_clock::Clock;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        CommitmentAccount, EscrowError, MIN_REVEAL_DELAY_SLOTS,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_commitment_pda, get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    struct Accounts {
+        maker: Pubkey,
+        escrow: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        vault: Pubkey,
+        treasury_ata_b: Pubkey,
+    }
+
+    fn make_escrow(litesvm: &mut LiteSVM, maker: &Keypair, give_amount: u64) -> Accounts {
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+
+        let escrow = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        Accounts {
+            maker: maker.pubkey(),
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            treasury_ata_b,
+        }
+    }
+
+    fn commit_ix(taker: &Pubkey, escrow: &Pubkey, hash: [u8; 32]) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*taker, true),
+                AccountMeta::new_readonly(*escrow, false),
+                AccountMeta::new(get_commitment_pda(escrow), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ],
+            data: [vec![20u8], hash.to_vec()].concat(),
+        }
+    }
+
+    fn reveal_ix(taker: &Pubkey, accounts: &Accounts, nonce: [u8; 32]) -> Instruction {
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(get_commitment_pda(&accounts.escrow), false),
+                AccountMeta::new(*taker, true),
+                AccountMeta::new(accounts.maker, false),
+                AccountMeta::new(accounts.escrow, false),
+                AccountMeta::new_readonly(accounts.mint_a, false),
+                AccountMeta::new_readonly(accounts.mint_b, false),
+                AccountMeta::new(accounts.vault, false),
+                AccountMeta::new(
+                    get_associated_token_address_with_program_id(
+                        taker,
+                        &accounts.mint_a,
+                        &TOKEN_PROGRAM_ID,
+                    ),
+                    false,
+                ),
+                AccountMeta::new(
+                    get_associated_token_address_with_program_id(
+                        taker,
+                        &accounts.mint_b,
+                        &TOKEN_PROGRAM_ID,
+                    ),
+                    false,
+                ),
+                AccountMeta::new(
+                    get_associated_token_address_with_program_id(
+                        &accounts.maker,
+                        &accounts.mint_b,
+                        &TOKEN_PROGRAM_ID,
+                    ),
+                    false,
+                ),
+                AccountMeta::new(accounts.maker, false),
+                AccountMeta::new(accounts.treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![21u8], nonce.to_vec()].concat(),
+        }
+    }
+
+    #[test]
+    fn reveal_take() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let give_amount: u64 = 500_000_000;
+        let accounts = make_escrow(litesvm, &maker, give_amount);
+        init_ata(litesvm, accounts.mint_b, taker.pubkey(), 1_000_000_000);
+
+        let nonce = [7u8; 32];
+        let hash = CommitmentAccount::hash(&taker.pubkey().to_bytes(), &nonce);
+        let commitment = get_commitment_pda(&accounts.escrow);
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[commit_ix(&taker.pubkey(), &accounts.escrow, hash)],
+        );
+
+        assert!(result.is_ok());
+
+        // The reveal can't land in the same window as the commitment
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[reveal_ix(&taker.pubkey(), &accounts, nonce)],
+            EscrowError::RevealTooEarly.into(),
+        );
+
+        // A second commitment can't replace a pending one
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[commit_ix(&maker.pubkey(), &accounts.escrow, [1u8; 32])],
+            ProgramError::AccountAlreadyInitialized,
+        );
+
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        clock.slot += MIN_REVEAL_DELAY_SLOTS;
+        litesvm.set_sysvar::<Clock>(&clock);
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[reveal_ix(&taker.pubkey(), &accounts, nonce)],
+        );
+
+        assert!(result.is_ok());
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &accounts.mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+        assert!(litesvm.get_account(&accounts.escrow).is_none());
+        assert!(litesvm.get_account(&commitment).is_none());
+    }
+
+    #[test]
+    fn reveal_take_with_mismatched_reveal() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let other = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let give_amount: u64 = 500_000_000;
+        let accounts = make_escrow(litesvm, &maker, give_amount);
+        init_ata(litesvm, accounts.mint_b, taker.pubkey(), 1_000_000_000);
+        init_ata(litesvm, accounts.mint_b, other.pubkey(), 1_000_000_000);
+
+        let nonce = [7u8; 32];
+        let hash = CommitmentAccount::hash(&taker.pubkey().to_bytes(), &nonce);
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[commit_ix(&taker.pubkey(), &accounts.escrow, hash)],
+        );
+
+        assert!(result.is_ok());
+
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        clock.slot += MIN_REVEAL_DELAY_SLOTS;
+        litesvm.set_sysvar::<Clock>(&clock);
+
+        // A wrong nonce doesn't match the commitment
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[reveal_ix(&taker.pubkey(), &accounts, [8u8; 32])],
+            EscrowError::CommitmentMismatch.into(),
+        );
+
+        // Neither does the right nonce revealed by someone else
+        simulate_expect_error(
+            litesvm,
+            &[&other],
+            &other.pubkey(),
+            &[reveal_ix(&other.pubkey(), &accounts, nonce)],
+            EscrowError::CommitmentMismatch.into(),
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &accounts.vault).amount,
+            give_amount
+        );
+    }
+}
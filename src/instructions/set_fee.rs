@@ -0,0 +1,175 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+};
+
+use crate::{
+    AccountCheck, CONFIG_SEED, Config, ConfigAccount, EscrowError, MAX_FEE_BPS, SignerAccount,
+    read_bytes,
+};
+
+pub struct SetFeeAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetFeeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self { admin, config })
+    }
+}
+
+pub struct SetFeeInstructionData {
+    pub fee_bps: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetFeeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u16>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let fee_bps = u16::from_le_bytes(read_bytes(data, 0)?);
+
+        // Instruction Checks
+        if fee_bps > MAX_FEE_BPS {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { fee_bps })
+    }
+}
+
+pub struct SetFee<'a> {
+    pub accounts: SetFeeAccounts<'a>,
+    pub instruction_data: SetFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetFee<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetFeeAccounts::try_from(accounts)?;
+        let instruction_data = SetFeeInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetFee<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut())?;
+
+        // Check if the admin signed for the config
+        if config.admin.ne(self.accounts.admin.key()) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Check if the config is valid
+        let config_key = create_program_address(&[CONFIG_SEED, &config.bump], &crate::ID)
+            .map_err(|_| EscrowError::InvalidConfig)?;
+        if &config_key != self.accounts.config.key() {
+            return Err(EscrowError::InvalidConfig.into());
+        }
+
+        config.set_fee_bps(self.instruction_data.fee_bps);
+
+        // Log the config and new fee
+        sol_log("Instruction: SetFee");
+        pubkey::log(self.accounts.config.key());
+        sol_log_64(self.instruction_data.fee_bps as u64, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Config, MAX_FEE_BPS,
+        tests::{
+            constants::PROGRAM_ID,
+            pda::get_config_pda,
+            utils::{build_and_send_transaction, init_wallet, setup, simulate_expect_error},
+        },
+    };
+
+    #[test]
+    fn set_fee() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let attacker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let config_pda = get_config_pda();
+
+        let set_fee_ix = |admin, fee_bps: u16| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new(config_pda, false),
+            ],
+            data: [vec![23u8], fee_bps.to_le_bytes().to_vec()].concat(),
+        };
+
+        // Only the admin can change the fee
+        simulate_expect_error(
+            litesvm,
+            &[&attacker],
+            &attacker.pubkey(),
+            &[set_fee_ix(attacker.pubkey(), 50)],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        // The fee is capped well below the whole receive amount
+        simulate_expect_error(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_fee_ix(default_payer.pubkey(), MAX_FEE_BPS + 1)],
+            ProgramError::InvalidInstructionData,
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_fee_ix(default_payer.pubkey(), 50)],
+        );
+
+        assert!(result.is_ok());
+
+        let config_acc = litesvm.get_account(&config_pda).unwrap();
+
+        assert_eq!(
+            Config::load(config_acc.data.as_ref()).unwrap().fee_bps(),
+            50
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey, create_program_address},
+};
+
+use crate::{
+    AccountCheck, CONFIG_SEED, Config, ConfigAccount, EscrowError, SignerAccount, read_bytes,
+};
+
+pub struct SetFeeRecipientAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetFeeRecipientAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self { admin, config })
+    }
+}
+
+pub struct SetFeeRecipientInstructionData {
+    pub fee_recipient: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetFeeRecipientInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let fee_recipient: Pubkey = read_bytes(data, 0)?;
+
+        // Instruction Checks
+        if fee_recipient == [0u8; 32] {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { fee_recipient })
+    }
+}
+
+pub struct SetFeeRecipient<'a> {
+    pub accounts: SetFeeRecipientAccounts<'a>,
+    pub instruction_data: SetFeeRecipientInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetFeeRecipient<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetFeeRecipientAccounts::try_from(accounts)?;
+        let instruction_data = SetFeeRecipientInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetFeeRecipient<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut())?;
+
+        // Check if the admin signed for the config
+        if config.admin.ne(self.accounts.admin.key()) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Check if the config is valid
+        let config_key = create_program_address(&[CONFIG_SEED, &config.bump], &crate::ID)
+            .map_err(|_| EscrowError::InvalidConfig)?;
+        if &config_key != self.accounts.config.key() {
+            return Err(EscrowError::InvalidConfig.into());
+        }
+
+        config.set_fee_recipient(self.instruction_data.fee_recipient);
+
+        // Log the config and new fee recipient
+        sol_log("Instruction: SetFeeRecipient");
+        pubkey::log(self.accounts.config.key());
+        pubkey::log(&self.instruction_data.fee_recipient);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_pubkey::Pubkey;
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Config, Escrow, EscrowError, MAX_FEE_BPS, split_fee,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn set_fee_recipient() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let fee_recipient = Keypair::new();
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let fee_recipient_ata_b = init_ata(litesvm, mint_b, fee_recipient.pubkey(), 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let config_pda = get_config_pda();
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let fee_bps: u16 = 50;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let set_fee_recipient_ix = |admin, fee_recipient: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new(config_pda, false),
+            ],
+            data: [vec![24u8], fee_recipient.to_bytes().to_vec()].concat(),
+        };
+        let set_fee_ix = |fee_bps: u16| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(default_payer.pubkey(), true),
+                AccountMeta::new(config_pda, false),
+            ],
+            data: [vec![23u8], fee_bps.to_le_bytes().to_vec()].concat(),
+        };
+
+        // Only the admin can redirect the fee
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[set_fee_recipient_ix(maker.pubkey(), maker.pubkey())],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[
+                set_fee_ix(fee_bps),
+                set_fee_recipient_ix(default_payer.pubkey(), fee_recipient.pubkey()),
+            ],
+        );
+
+        assert!(result.is_ok());
+
+        let config_acc = litesvm.get_account(&config_pda).unwrap();
+
+        assert_eq!(
+            Config::load(config_acc.data.as_ref())
+                .unwrap()
+                .fee_recipient(),
+            &fee_recipient.pubkey().to_bytes()
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_pda, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // Raising the fee afterwards doesn't change the rate the escrow was opened at
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_fee_ix(MAX_FEE_BPS)],
+        );
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+
+        assert_eq!(
+            Escrow::load(escrow_acc.data.as_ref()).unwrap().fee_bps(),
+            fee_bps
+        );
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let take_ix = |treasury_ata_b: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_pda, false),
+            ],
+            data: vec![1u8],
+        };
+
+        // The old treasury no longer receives the fee
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(treasury_ata_b)],
+            EscrowError::InvalidTreasury.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(fee_recipient_ata_b)],
+        );
+
+        assert!(result.is_ok());
+
+        let (fee, maker_receive) = split_fee(receive_amount, fee_bps).unwrap();
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &fee_recipient_ata_b).amount,
+            fee
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            maker_receive
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &treasury_ata_b).amount,
+            0
+        );
+    }
+}
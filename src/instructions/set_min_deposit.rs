@@ -0,0 +1,220 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+};
+
+use crate::{
+    AccountCheck, CONFIG_SEED, Config, ConfigAccount, EscrowError, SignerAccount, read_bytes,
+};
+
+pub struct SetMinDepositAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetMinDepositAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self { admin, config })
+    }
+}
+
+pub struct SetMinDepositInstructionData {
+    pub min_deposit: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetMinDepositInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let min_deposit = u64::from_le_bytes(read_bytes(data, 0)?);
+
+        Ok(Self { min_deposit })
+    }
+}
+
+pub struct SetMinDeposit<'a> {
+    pub accounts: SetMinDepositAccounts<'a>,
+    pub instruction_data: SetMinDepositInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetMinDeposit<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetMinDepositAccounts::try_from(accounts)?;
+        let instruction_data = SetMinDepositInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetMinDeposit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut())?;
+
+        // Check if the admin signed for the config
+        if config.admin.ne(self.accounts.admin.key()) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Check if the config is valid
+        let config_key = create_program_address(&[CONFIG_SEED, &config.bump], &crate::ID)
+            .map_err(|_| EscrowError::InvalidConfig)?;
+        if &config_key != self.accounts.config.key() {
+            return Err(EscrowError::InvalidConfig.into());
+        }
+
+        config.set_min_deposit(self.instruction_data.min_deposit);
+
+        // Log the config and new minimum deposit
+        sol_log("Instruction: SetMinDeposit");
+        pubkey::log(self.accounts.config.key());
+        sol_log_64(self.instruction_data.min_deposit, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Config, EscrowError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn set_min_deposit() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let config_pda = get_config_pda();
+        let min_deposit: u64 = 1_000_000;
+
+        let set_min_deposit_ix = |admin, min_deposit: u64| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new(config_pda, false),
+            ],
+            data: [vec![26u8], min_deposit.to_le_bytes().to_vec()].concat(),
+        };
+
+        // Only the admin can change the minimum deposit
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[set_min_deposit_ix(maker.pubkey(), 0)],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_min_deposit_ix(default_payer.pubkey(), min_deposit)],
+        );
+
+        assert!(result.is_ok());
+
+        let config_acc = litesvm.get_account(&config_pda).unwrap();
+
+        assert_eq!(
+            Config::load(config_acc.data.as_ref())
+                .unwrap()
+                .min_deposit(),
+            min_deposit
+        );
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let make_ix = |give_amount: u64| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_pda, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![1u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        // Make enforces the new minimum instead of the InitConfig default
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[make_ix(min_deposit - 1)],
+            EscrowError::DepositTooSmall.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[make_ix(min_deposit)],
+        );
+
+        assert!(result.is_ok());
+    }
+}
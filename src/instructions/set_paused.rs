@@ -0,0 +1,225 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address},
+};
+
+use crate::{AccountCheck, CONFIG_SEED, Config, ConfigAccount, EscrowError, SignerAccount};
+
+pub struct SetPausedAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetPausedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(admin)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self { admin, config })
+    }
+}
+
+pub struct SetPausedInstructionData {
+    pub paused: bool,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetPausedInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u8>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // Instruction Checks
+        let paused = match data[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { paused })
+    }
+}
+
+pub struct SetPaused<'a> {
+    pub accounts: SetPausedAccounts<'a>,
+    pub instruction_data: SetPausedInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetPaused<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetPausedAccounts::try_from(accounts)?;
+        let instruction_data = SetPausedInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetPaused<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = Config::load_mut(data.as_mut())?;
+
+        // Check if the admin signed for the config
+        if config.admin.ne(self.accounts.admin.key()) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Check if the config is valid
+        let config_key = create_program_address(&[CONFIG_SEED, &config.bump], &crate::ID)
+            .map_err(|_| EscrowError::InvalidConfig)?;
+        if &config_key != self.accounts.config.key() {
+            return Err(EscrowError::InvalidConfig.into());
+        }
+
+        config.set_paused(self.instruction_data.paused);
+
+        // Log the config and new pause state
+        sol_log("Instruction: SetPaused");
+        pubkey::log(self.accounts.config.key());
+        sol_log_64(self.instruction_data.paused as u64, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Config, EscrowError,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn set_paused() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let config_pda = get_config_pda();
+        let seed = 42u64;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let set_paused_ix = |admin, paused: bool| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(admin, true),
+                AccountMeta::new(config_pda, false),
+            ],
+            data: vec![14u8, paused as u8],
+        };
+
+        let make_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(config_pda, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                100_000_000u64.to_le_bytes().to_vec(),
+                500_000_000u64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        // Only the admin can pause
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[set_paused_ix(maker.pubkey(), true)],
+            ProgramError::MissingRequiredSignature,
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_paused_ix(default_payer.pubkey(), true)],
+        );
+
+        assert!(result.is_ok());
+
+        let config_acc = litesvm.get_account(&config_pda).unwrap();
+        assert!(Config::load(config_acc.data.as_ref()).unwrap().paused);
+
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            core::slice::from_ref(&make_ix),
+            EscrowError::Paused.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[set_paused_ix(default_payer.pubkey(), false)],
+        );
+
+        assert!(result.is_ok());
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[make_ix]);
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_some());
+    }
+}
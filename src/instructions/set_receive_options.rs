@@ -0,0 +1,153 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, Pubkey, create_program_address},
+};
+
+use crate::{
+    AccountCheck, ESCROW_SEED_PREFIX, Escrow, EscrowError, MAX_RECEIVE_OPTIONS, NATIVE_MINT,
+    ProgramAccount, ReceiveOption, ReceiveOptions, SignerAccount, assert_is_maker, read_bytes,
+};
+
+pub struct SetReceiveOptionsAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetReceiveOptionsAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+
+        // Return the accounts
+        Ok(Self { maker, escrow })
+    }
+}
+
+pub struct SetReceiveOptionsInstructionData {
+    pub options: ReceiveOptions,
+    pub len: usize,
+}
+
+impl SetReceiveOptionsInstructionData {
+    // Each option is a mint followed by the amount wanted in it
+    pub const OPTION_LEN: usize = size_of::<Pubkey>() + size_of::<u64>();
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetReceiveOptionsInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // An empty payload clears every option
+        if !data.len().is_multiple_of(Self::OPTION_LEN)
+            || data.len() / Self::OPTION_LEN > MAX_RECEIVE_OPTIONS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let len = data.len() / Self::OPTION_LEN;
+        let mut options = ReceiveOptions::default();
+
+        for index in 0..len {
+            let offset = index * Self::OPTION_LEN;
+            let mint: Pubkey = read_bytes(data, offset)?;
+            let receive = u64::from_le_bytes(read_bytes(data, offset + size_of::<Pubkey>())?);
+
+            // Instruction Checks
+            if receive == 0 {
+                return Err(EscrowError::ZeroAmount.into());
+            }
+
+            if mint == [0u8; 32] || options[..index].iter().any(|option| option.mint == mint) {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            // Lamport payments only go through TakeNative against mint B
+            if mint == NATIVE_MINT {
+                return Err(EscrowError::ReceiveAssetMismatch.into());
+            }
+
+            options[index] = ReceiveOption { mint, receive };
+        }
+
+        Ok(Self { options, len })
+    }
+}
+
+pub struct SetReceiveOptions<'a> {
+    pub accounts: SetReceiveOptionsAccounts<'a>,
+    pub instruction_data: SetReceiveOptionsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetReceiveOptions<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetReceiveOptionsAccounts::try_from(accounts)?;
+        let instruction_data = SetReceiveOptionsInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetReceiveOptions<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+
+        // Check if the maker signed for the escrow
+        assert_is_maker(escrow, self.accounts.maker)?;
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Options sit alongside mint B, so they can't repeat it or be the deposited token
+        for option in &self.instruction_data.options[..self.instruction_data.len] {
+            if option.mint.eq(&escrow.mint_a) {
+                return Err(EscrowError::SameMint.into());
+            }
+
+            if option.mint.eq(&escrow.mint_b) {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        // Unused options are left zeroed
+        for (index, option) in self.instruction_data.options.iter().enumerate() {
+            escrow.set_receive_option(index, option.mint, option.receive);
+        }
+
+        // Log the escrow and number of options
+        sol_log("Instruction: SetReceiveOptions");
+        pubkey::log(self.accounts.escrow.key());
+        sol_log_64(self.instruction_data.len as u64, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,739 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey::{self, create_program_address, find_program_address},
+    sysvars::{Sysvar, rent::Rent},
+};
+use pinocchio_token_2022::instructions::TransferChecked;
+
+use crate::{
+    AccountCheck, AssociatedTokenAccount, AssociatedTokenAccountCheck, AssociatedTokenAccountInit,
+    AssociatedTokenProgram, Config, ConfigAccount, ESCROW_SEED_PREFIX, Escrow, EscrowError,
+    MintInterface, ProgramAccount, ProgramAccountInit, SignerAccount, SystemProgram,
+    TokenAccountInterface, TokenProgramInterface, assert_is_maker, checked_mul_div, read_bytes,
+};
+
+pub struct SplitAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub new_escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub new_vault: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_account_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SplitAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            maker,
+            escrow,
+            new_escrow,
+            mint_a,
+            vault,
+            new_vault,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        ConfigAccount::check(config)?;
+
+        // Return the accounts
+        Ok(Self {
+            maker,
+            escrow,
+            new_escrow,
+            mint_a,
+            vault,
+            new_vault,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+        })
+    }
+}
+
+pub struct SplitInstructionData {
+    pub seed: u64,
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SplitInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let seed = u64::from_le_bytes(read_bytes(data, 0)?);
+        let amount = u64::from_le_bytes(read_bytes(data, 8)?);
+
+        // Instruction Checks
+        if amount == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        Ok(Self { seed, amount })
+    }
+}
+
+pub struct Split<'a> {
+    pub accounts: SplitAccounts<'a>,
+    pub instruction_data: SplitInstructionData,
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Split<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SplitAccounts::try_from(accounts)?;
+        let instruction_data = SplitInstructionData::try_from(data)?;
+
+        // Check if the protocol is paused before creating any accounts
+        ConfigAccount::check_not_paused(accounts.config)?;
+
+        // Check if the split off deposit meets the minimum, as Make does for a new escrow
+        if instruction_data.amount
+            < Config::load(&accounts.config.try_borrow_data()?)?.min_deposit()
+        {
+            return Err(EscrowError::DepositTooSmall.into());
+        }
+
+        // Initialize the new escrow under the maker, the same way Make does
+        let (escrow_key, bump) = find_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                accounts.maker.key(),
+                &instruction_data.seed.to_le_bytes(),
+            ],
+            &crate::ID,
+        );
+        if &escrow_key != accounts.new_escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        let seed_binding = instruction_data.seed.to_le_bytes();
+        let bump_binding = [bump];
+        let escrow_seeds = [
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(accounts.maker.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<Escrow>(
+            accounts.maker,
+            accounts.new_escrow,
+            &escrow_seeds,
+            Escrow::LEN,
+        )?;
+
+        // Initialize the new vault
+        AssociatedTokenAccount::init(
+            accounts.new_vault,
+            accounts.mint_a,
+            accounts.maker,
+            accounts.new_escrow,
+            accounts.system_program,
+            accounts.token_program,
+        )?;
+
+        // Check if the new vault is owned by the new escrow and holds mint A
+        AssociatedTokenAccount::check(
+            accounts.new_vault,
+            accounts.new_escrow,
+            accounts.mint_a,
+            accounts.token_program,
+        )?;
+
+        // Check if the new accounts were funded to be rent exempt
+        let rent = Rent::get()?;
+        for account in [accounts.new_escrow, accounts.new_vault] {
+            if account.lamports() < rent.minimum_balance(account.data_len()) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            bump,
+        })
+    }
+}
+
+impl<'a> Split<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let escrow = Escrow::from_bytes(&self.accounts.escrow.try_borrow_data()?)?;
+
+        // Check if the maker signed for the escrow
+        assert_is_maker(&escrow, self.accounts.maker)?;
+
+        // Check if the mint matches the escrow
+        if escrow.mint_a.ne(self.accounts.mint_a.key()) {
+            return Err(EscrowError::MintMismatch.into());
+        }
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| EscrowError::InvalidEscrowPda)?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Vesting schedules and basket entries aren't divided between the two escrows
+        if escrow.vesting_duration != 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
+
+        if !escrow.basket().is_empty() {
+            return Err(EscrowError::BasketMismatch.into());
+        }
+
+        // Check if both escrows are left with something to trade
+        let vault_amount = TokenAccountInterface::amount(self.accounts.vault)?;
+        if self.instruction_data.amount >= vault_amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Check if the original escrow keeps at least the minimum deposit
+        if vault_amount - self.instruction_data.amount
+            < Config::load(&self.accounts.config.try_borrow_data()?)?.min_deposit()
+        {
+            return Err(EscrowError::DepositTooSmall.into());
+        }
+
+        // Move the receive amount in proportion to the portion of the vault being split off
+        let receive = checked_mul_div(escrow.receive, self.instruction_data.amount, vault_amount)?;
+        if receive == 0 || receive == escrow.receive {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        // Populate the new escrow with the terms of the original
+        {
+            let mut data = self.accounts.new_escrow.try_borrow_mut_data()?;
+            let new_escrow = Escrow::load_mut_uninitialized(data.as_mut())?;
+
+            new_escrow.set_inner(
+                self.instruction_data.seed,
+                escrow.maker,
+                escrow.mint_a,
+                escrow.mint_b,
+                receive,
+                escrow.expiry,
+                escrow.start_time,
+                0,
+                0,
+                escrow.authorized_taker,
+                escrow.vault_rent_recipient,
+                escrow.memo,
+                escrow.mint_a_decimals,
+                escrow.mint_b_decimals,
+                escrow.receive_native,
+                [self.bump],
+                escrow.fee_bps,
+            );
+
+            // Move each accepted mint's amount in the same proportion
+            for (index, option) in escrow.receive_options().iter().enumerate() {
+                let option_receive =
+                    checked_mul_div(option.receive, self.instruction_data.amount, vault_amount)?;
+                if option_receive == 0 || option_receive == option.receive {
+                    return Err(EscrowError::ZeroAmount.into());
+                }
+
+                new_escrow.set_receive_option(index, option.mint, option_receive);
+            }
+        }
+
+        // Reduce the receive amounts of the original escrow
+        {
+            let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+            let original = Escrow::load_mut(data.as_mut())?;
+            original.set_receive(escrow.receive - receive);
+
+            for (index, option) in escrow.receive_options().iter().enumerate() {
+                original.set_receive_option(
+                    index,
+                    option.mint,
+                    option.receive
+                        - checked_mul_div(
+                            option.receive,
+                            self.instruction_data.amount,
+                            vault_amount,
+                        )?,
+                );
+            }
+        }
+
+        // Transfer the split off tokens to the new vault
+        let seed_binding = escrow.seed.to_le_bytes();
+        let escrow_seeds = [
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(&escrow.creator),
+            Seed::from(&seed_binding),
+            Seed::from(&escrow.bump),
+        ];
+
+        TransferChecked {
+            from: self.accounts.vault,
+            mint: self.accounts.mint_a,
+            to: self.accounts.new_vault,
+            authority: self.accounts.escrow,
+            amount: self.instruction_data.amount,
+            decimals: MintInterface::decimals(self.accounts.mint_a)?,
+            token_program: self.accounts.token_program.key(),
+        }
+        .invoke_signed(&[Signer::from(&escrow_seeds)])?;
+
+        // Log both escrows and the split off amounts
+        sol_log("Instruction: Split");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.new_escrow.key());
+        sol_log_64(self.instruction_data.amount, receive, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Escrow, EscrowError, MIN_DEPOSIT_AMOUNT,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+                simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn split() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let new_seed = 43u64;
+        let receive_amount: u64 = 100;
+        let give_amount: u64 = 500;
+        let split_amount: u64 = 200;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let new_escrow_pda = get_escrow_pda(&maker.pubkey(), new_seed);
+        let new_vault = get_vault_address(&new_escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(new_escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(new_vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![11u8],
+                new_seed.to_le_bytes().to_vec(),
+                split_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(fetch_account::<TokenAccount>(litesvm, &vault).amount, 300);
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &new_vault).amount,
+            200
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.seed, seed);
+        assert_eq!(escrow.receive, 60);
+
+        let new_escrow_acc = litesvm.get_account(&new_escrow_pda).unwrap();
+        let new_escrow = Escrow::load(new_escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(new_escrow.seed, new_seed);
+        assert_eq!(new_escrow.maker, maker.pubkey().to_bytes());
+        assert_eq!(new_escrow.creator, maker.pubkey().to_bytes());
+        assert_eq!(new_escrow.mint_a, mint_a.to_bytes());
+        assert_eq!(new_escrow.mint_b, mint_b.to_bytes());
+        assert_eq!(new_escrow.receive, 40);
+        assert_eq!(new_escrow.vault_rent_recipient, maker.pubkey().to_bytes());
+    }
+
+    #[test]
+    fn split_whole_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let new_seed = 43u64;
+        let receive_amount: u64 = 100;
+        let give_amount: u64 = 500;
+        let split_amount: u64 = give_amount;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let new_escrow_pda = get_escrow_pda(&maker.pubkey(), new_seed);
+        let new_vault = get_vault_address(&new_escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(new_escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(new_vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![11u8],
+                new_seed.to_le_bytes().to_vec(),
+                split_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        // Splitting off the whole vault would leave the original escrow empty
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[ix],
+            ProgramError::InsufficientFunds,
+        );
+    }
+
+    #[test]
+    fn split_with_min_deposit_and_pause() {
+        let (litesvm, default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let new_seed = 43u64;
+        let receive_amount: u64 = 100;
+        let give_amount: u64 = 500;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let new_escrow_pda = get_escrow_pda(&maker.pubkey(), new_seed);
+        let new_vault = get_vault_address(&new_escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let split_ix = |split_amount: u64| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(new_escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(new_vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![11u8],
+                new_seed.to_le_bytes().to_vec(),
+                split_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        // Neither the split off deposit nor the one left behind can fall below the minimum
+        for split_amount in [MIN_DEPOSIT_AMOUNT - 1, give_amount - MIN_DEPOSIT_AMOUNT + 1] {
+            simulate_expect_error(
+                litesvm,
+                &[&maker],
+                &maker.pubkey(),
+                &[split_ix(split_amount)],
+                EscrowError::DepositTooSmall.into(),
+            );
+        }
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[default_payer],
+            &default_payer.pubkey(),
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new_readonly(default_payer.pubkey(), true),
+                    AccountMeta::new(get_config_pda(), false),
+                ],
+                data: vec![14u8, 1],
+            }],
+        );
+
+        assert!(result.is_ok());
+
+        // Split opens a new escrow, so it stops while the protocol is paused
+        simulate_expect_error(
+            litesvm,
+            &[&maker],
+            &maker.pubkey(),
+            &[split_ix(200)],
+            EscrowError::Paused.into(),
+        );
+    }
+
+    #[test]
+    fn split_with_non_pda_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let new_seed = 43u64;
+        let receive_amount: u64 = 100;
+        let give_amount: u64 = 500;
+        let split_amount: u64 = 200;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        // A keypair account the maker signs for instead of the escrow PDA for the new seed
+        let new_escrow = Keypair::new();
+        let new_vault = get_vault_address(&new_escrow.pubkey(), &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(new_escrow.pubkey(), true),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(new_vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![11u8],
+                new_seed.to_le_bytes().to_vec(),
+                split_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        // No later instruction could derive the address, locking the split off tokens
+        simulate_expect_error(
+            litesvm,
+            &[&maker, &new_escrow],
+            &maker.pubkey(),
+            &[ix],
+            EscrowError::InvalidEscrowPda.into(),
+        );
+    }
+}
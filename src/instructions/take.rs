@@ -1,18 +1,23 @@
+use core::mem::size_of;
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
+    cpi::set_return_data,
     instruction::{Seed, Signer},
+    log::{sol_log, sol_log_64},
     program_error::ProgramError,
-    pubkey::create_program_address,
-};
-use pinocchio_token::{
-    instructions::{CloseAccount, Transfer},
-    state::TokenAccount,
+    pubkey::{self, Pubkey, create_program_address, find_program_address},
+    sysvars::{Sysvar, clock::Clock},
 };
+use pinocchio_system::instructions::Transfer;
+use pinocchio_token_2022::instructions::{CloseAccount, TransferChecked};
 
 use crate::{
     AccountCheck, AccountClose, AssociatedTokenAccount, AssociatedTokenAccountCheck,
-    AssociatedTokenAccountInit, Escrow, MintInterface, ProgramAccount, SignerAccount,
+    AssociatedTokenAccountInit, AssociatedTokenProgram, Config, ConfigAccount, ESCROW_SEED_PREFIX,
+    Escrow, EscrowError, HarvestWithheldTokensToMint, MintInterface, ProgramAccount, SignerAccount,
+    SystemAccount, SystemProgram, TRANSFER_FEE_CONFIG_EXTENSION, TokenAccountInterface,
+    TokenProgramInterface, account_groups, checked_mul_div_ceil, read_bytes, split_fee,
 };
 
 pub struct TakeAccounts<'a> {
@@ -22,12 +27,18 @@ pub struct TakeAccounts<'a> {
     pub mint_a: &'a AccountInfo,
     pub mint_b: &'a AccountInfo,
     pub vault: &'a AccountInfo,
+    // Owner of taker_ata_a, the taker unless TakeTo names another recipient
+    pub recipient: &'a AccountInfo,
     pub taker_ata_a: &'a AccountInfo,
     pub taker_ata_b: &'a AccountInfo,
     pub maker_ata_b: &'a AccountInfo,
+    pub vault_rent_recipient: &'a AccountInfo,
+    pub treasury_ata_b: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub associated_token_account_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub basket: &'a [AccountInfo],
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for TakeAccounts<'a> {
@@ -44,139 +55,596 @@ impl<'a> TryFrom<&'a [AccountInfo]> for TakeAccounts<'a> {
             taker_ata_a,
             taker_ata_b,
             maker_ata_b,
+            vault_rent_recipient,
+            treasury_ata_b,
             system_program,
             token_program,
             associated_token_account_program,
+            config,
+            basket @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        // A mint, vault and taker ATA follow for each basket entry, anything left over is surplus
+        if basket.len() % 3 != 0 {
+            return Err(EscrowError::TooManyAccountKeys.into());
+        }
+
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        ConfigAccount::check(config)?;
         SignerAccount::check(taker)?;
         ProgramAccount::check(escrow)?;
         MintInterface::check(mint_a)?;
         MintInterface::check(mint_b)?;
         AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
-        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
 
-        // Return the accounts
+        // Return the accounts, in the order they were passed
         Ok(Self {
             taker,
             maker,
             escrow,
             mint_a,
             mint_b,
+            vault,
+            recipient: taker,
             taker_ata_a,
             taker_ata_b,
             maker_ata_b,
-            vault,
+            vault_rent_recipient,
+            treasury_ata_b,
             system_program,
             token_program,
             associated_token_account_program,
+            config,
+            basket,
         })
     }
 }
 
+pub struct TakeInstructionData {
+    pub fill_amount: Option<u64>,
+    pub max_receive: u64,
+    pub expected_vault_amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for TakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        match data.len() {
+            // An empty payload fills the whole offer
+            0 => Ok(Self {
+                fill_amount: None,
+                max_receive: 0,
+                expected_vault_amount: 0,
+            }),
+            8 => {
+                let fill_amount = u64::from_le_bytes(read_bytes(data, 0)?);
+
+                // Instruction Checks
+                if fill_amount == 0 {
+                    return Err(EscrowError::ZeroAmount.into());
+                }
+
+                Ok(Self {
+                    fill_amount: Some(fill_amount),
+                    max_receive: 0,
+                    expected_vault_amount: 0,
+                })
+            }
+            16 => {
+                let fill_amount = u64::from_le_bytes(read_bytes(data, 0)?);
+                let max_receive = u64::from_le_bytes(read_bytes(data, 8)?);
+
+                // A zero fill amount alongside max_receive fills the whole offer
+                Ok(Self {
+                    fill_amount: (fill_amount != 0).then_some(fill_amount),
+                    max_receive,
+                    expected_vault_amount: 0,
+                })
+            }
+            24 => {
+                let fill_amount = u64::from_le_bytes(read_bytes(data, 0)?);
+                let max_receive = u64::from_le_bytes(read_bytes(data, 8)?);
+                let expected_vault_amount = u64::from_le_bytes(read_bytes(data, 16)?);
+
+                // Zero fill amount, max_receive and expected vault amount each fall back as above
+                Ok(Self {
+                    fill_amount: (fill_amount != 0).then_some(fill_amount),
+                    max_receive,
+                    expected_vault_amount,
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
 pub struct Take<'a> {
     pub accounts: TakeAccounts<'a>,
+    pub instruction_data: TakeInstructionData,
+    // Set by TakeNative to pay the maker in lamports instead of mint B
+    pub native: bool,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Take<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Take<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = TakeAccounts::try_from(accounts)?;
+        let instruction_data = TakeInstructionData::try_from(data)?;
+
+        Take::new(accounts, instruction_data)
+    }
+}
+
+impl<'a> Take<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    // Fields are only ever appended, so decoders can rely on these offsets for every version
+    pub const RETURN_DATA_VERSION: u8 = 1;
+
+    // Version, status, mint A, mint B, amount filled, amount paid and reclaimed lamports
+    pub const RETURN_DATA_LEN: usize = size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<u64>()
+        + size_of::<u64>();
+
+    // Status of the escrow after the Take
+    pub const STATUS_PARTIALLY_FILLED: u8 = 0;
+    pub const STATUS_FILLED: u8 = 1;
+
+    pub fn new(
+        accounts: TakeAccounts<'a>,
+        instruction_data: TakeInstructionData,
+    ) -> Result<Self, ProgramError> {
+        // Check if the protocol is paused before creating any ATAs
+        ConfigAccount::check_not_paused(accounts.config)?;
+
+        // Check if the maker and mints match the escrow before creating any ATAs
+        let receive_native = {
+            let data = accounts.escrow.try_borrow_data()?;
+            let escrow = Escrow::load(&data)?;
+
+            if escrow.maker.ne(accounts.maker.key()) {
+                return Err(EscrowError::MakerMismatch.into());
+            }
+
+            // Mint B may be any of the mints the maker accepts as payment
+            if escrow.mint_a.ne(accounts.mint_a.key())
+                || escrow.receive_for(accounts.mint_b.key()).is_none()
+            {
+                return Err(EscrowError::MintMismatch.into());
+            }
+
+            escrow.receive_native && escrow.mint_b.eq(accounts.mint_b.key())
+        };
+
+        // Check if the vault is owned by the escrow and holds mint A
+        AssociatedTokenAccount::check(
+            accounts.vault,
+            accounts.escrow,
+            accounts.mint_a,
+            accounts.token_program,
+        )?;
+
+        // Paying the vault out to itself would leave nothing for the taker once it is closed
+        if accounts.taker_ata_a.key().eq(accounts.vault.key()) {
+            return Err(EscrowError::SameTokenAccount.into());
+        }
 
         // Initialize necessary accounts
         AssociatedTokenAccount::init_if_needed(
             accounts.taker_ata_a,
             accounts.mint_a,
             accounts.taker,
-            accounts.taker,
+            accounts.recipient,
             accounts.system_program,
             accounts.token_program,
         )?;
 
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_b,
-            accounts.mint_b,
-            accounts.taker,
-            accounts.maker,
-            accounts.system_program,
+        // Check if the recipient ATA holds mint A, whether it was just created or already existed
+        AssociatedTokenAccount::check(
+            accounts.taker_ata_a,
+            accounts.recipient,
+            accounts.mint_a,
             accounts.token_program,
         )?;
 
-        Ok(Self { accounts })
-    }
-}
+        // Escrows paid in lamports have no maker ATA B
+        if !receive_native {
+            // Only live wallets get an ATA B created for them, other makers and closed wallets
+            // must create it beforehand
+            if accounts.maker_ata_b.data_is_empty()
+                && (SystemAccount::check(accounts.maker).is_err() || accounts.maker.lamports() == 0)
+            {
+                return Err(EscrowError::InvalidMaker.into());
+            }
 
-impl<'a> Take<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &1;
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_b,
+                accounts.mint_b,
+                accounts.taker,
+                accounts.maker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        for [mint, _, taker_ata] in account_groups::<3>(accounts.basket) {
+            AssociatedTokenAccount::init_if_needed(
+                taker_ata,
+                mint,
+                accounts.taker,
+                accounts.recipient,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            native: false,
+        })
+    }
 
     pub fn process(&mut self) -> ProgramResult {
-        let data = self.accounts.escrow.try_borrow_data()?;
-        let escrow = Escrow::load(&data)?;
+        // Work on a copy so the Escrow data isn't borrowed across the CPIs below
+        let escrow = Escrow::from_bytes(&self.accounts.escrow.try_borrow_data()?)?;
+
+        // Check if the taker is not the maker
+        if self.accounts.taker.key() == self.accounts.maker.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Check if the escrow is paid in the asset this instruction pays with, receive options
+        // are always paid in tokens
+        let paid_native = escrow.receive_native && escrow.mint_b.eq(self.accounts.mint_b.key());
+        if paid_native != self.native {
+            return Err(EscrowError::ReceiveAssetMismatch.into());
+        }
+
+        // Amount wanted in the mint the taker pays with
+        let base_receive = escrow
+            .receive_for(self.accounts.mint_b.key())
+            .ok_or(EscrowError::MintMismatch)?;
+
+        // The fee recipient is set by the admin, Take::new already checked the config
+        let fee_recipient = Config::load(&self.accounts.config.try_borrow_data()?)?.fee_recipient;
+
+        // Check if the treasury ATA belongs to the fee recipient and holds mint B, or is the fee
+        // recipient itself when paying in lamports
+        if self.native {
+            if self.accounts.treasury_ata_b.key().ne(&fee_recipient) {
+                return Err(EscrowError::InvalidTreasury.into());
+            }
+        } else {
+            TokenAccountInterface::check(self.accounts.treasury_ata_b)?;
+            if TokenAccountInterface::owner(self.accounts.treasury_ata_b)?.ne(&fee_recipient)
+                || TokenAccountInterface::mint(self.accounts.treasury_ata_b)?
+                    .ne(self.accounts.mint_b.key())
+            {
+                return Err(EscrowError::InvalidTreasury.into());
+            }
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        // Check if the escrow has expired
+        if escrow.expiry != 0 && now > escrow.expiry {
+            return Err(EscrowError::Expired.into());
+        }
+
+        // Check if the escrow can be taken yet
+        if escrow.start_time != 0 && now < escrow.start_time {
+            return Err(EscrowError::NotStarted.into());
+        }
+
+        // Check if the taker is allowed to fill the escrow
+        if escrow.authorized_taker.ne(&[0u8; 32])
+            && escrow.authorized_taker.ne(self.accounts.taker.key())
+        {
+            return Err(EscrowError::UnauthorizedTaker.into());
+        }
+
+        // Check if the vault rent goes where the maker asked
+        if escrow
+            .vault_rent_recipient
+            .ne(self.accounts.vault_rent_recipient.key())
+        {
+            return Err(EscrowError::VaultRentRecipientMismatch.into());
+        }
+
+        // Vesting escrows are released through Claim instead
+        if escrow.vesting_duration != 0 {
+            return Err(EscrowError::VestingMismatch.into());
+        }
 
         // Check if the escrow is valid
         let escrow_key = create_program_address(
             &[
-                b"escrow",
-                self.accounts.maker.key(),
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
                 &escrow.seed.to_le_bytes(),
                 &escrow.bump,
             ],
             &crate::ID,
-        )?;
+        )
+        .map_err(|_| EscrowError::InvalidEscrowPda)?;
         if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(EscrowError::InvalidEscrowPda.into());
         }
 
+        // Make only ever stores the canonical bump
+        debug_assert_eq!(
+            find_program_address(
+                &[
+                    ESCROW_SEED_PREFIX,
+                    &escrow.creator,
+                    &escrow.seed.to_le_bytes(),
+                ],
+                &crate::ID,
+            )
+            .1,
+            escrow.bump[0]
+        );
+
         let seed_binding = escrow.seed.to_le_bytes();
-        let bump_binding = escrow.bump;
         let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(self.accounts.maker.key().as_ref()),
+            Seed::from(ESCROW_SEED_PREFIX),
+            Seed::from(&escrow.creator),
             Seed::from(&seed_binding),
-            Seed::from(&bump_binding),
+            Seed::from(&escrow.bump),
         ];
         let signer = Signer::from(&escrow_seeds);
 
-        let amount = {
-            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+        // A frozen vault can neither be emptied nor closed
+        if TokenAccountInterface::is_frozen(self.accounts.vault)? {
+            return Err(EscrowError::VaultFrozen.into());
+        }
+
+        // Check if there is anything left to take
+        let vault_amount = TokenAccountInterface::amount(self.accounts.vault)?;
+        if vault_amount == 0 {
+            return Err(EscrowError::EmptyVault.into());
+        }
+
+        // Check if the vault still holds what the taker saw when submitting
+        if self.instruction_data.expected_vault_amount != 0
+            && vault_amount != self.instruction_data.expected_vault_amount
+        {
+            return Err(EscrowError::VaultAmountMismatch.into());
+        }
+
+        let amount = self.instruction_data.fill_amount.unwrap_or(vault_amount);
 
-            vault.amount()
+        if amount > vault_amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Scale the amount owed to the portion of the vault being filled, rounding up in the
+        // maker's favour so dust fills can't withdraw tokens for free
+        let is_full_fill = amount == vault_amount;
+        let receive = if is_full_fill {
+            base_receive
+        } else {
+            checked_mul_div_ceil(base_receive, amount, vault_amount)?
         };
+        if receive == 0 {
+            return Err(EscrowError::ZeroAmount.into());
+        }
+
+        // Check if the taker is paying more than they agreed to, zero disables the check
+        if self.instruction_data.max_receive != 0 && receive > self.instruction_data.max_receive {
+            return Err(EscrowError::SlippageExceeded.into());
+        }
+
+        // Check if the taker can pay before the vault is emptied, rather than failing in the
+        // token program with an opaque error
+        if !self.native && TokenAccountInterface::amount(self.accounts.taker_ata_b)? < receive {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Check if there is a mint and vault for each basket entry, which is only taken whole
+        if self.accounts.basket.len() != escrow.basket().len() * 3
+            || (!is_full_fill && !escrow.basket().is_empty())
+        {
+            return Err(EscrowError::BasketMismatch.into());
+        }
+        for (entry, [mint, vault, _]) in escrow
+            .basket()
+            .iter()
+            .zip(account_groups::<3>(self.accounts.basket))
+        {
+            if entry.mint.ne(mint.key()) {
+                return Err(EscrowError::BasketMismatch.into());
+            }
+            AssociatedTokenAccount::check(
+                vault,
+                self.accounts.escrow,
+                mint,
+                self.accounts.token_program,
+            )?;
+        }
 
         // Transfer from the Vault to the Taker
-        Transfer {
+        TransferChecked {
             from: self.accounts.vault,
+            mint: self.accounts.mint_a,
             to: self.accounts.taker_ata_a,
             authority: self.accounts.escrow,
             amount,
+            decimals: escrow.mint_a_decimals,
+            token_program: self.accounts.token_program.key(),
         }
-        .invoke_signed(&[signer.clone()])?;
+        .invoke_signed(core::slice::from_ref(&signer))?;
 
-        // Close the Vault
-        CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
+        // Track the rent reclaimed from closing accounts
+        let mut reclaimed_lamports = 0u64;
+
+        // Close the Vault once it has been emptied
+        if is_full_fill {
+            // Harvest fees withheld in the Vault, which would otherwise block closing it
+            if MintInterface::has_extension(self.accounts.mint_a, TRANSFER_FEE_CONFIG_EXTENSION)? {
+                HarvestWithheldTokensToMint {
+                    mint: self.accounts.mint_a,
+                    source: self.accounts.vault,
+                }
+                .invoke()?;
+            }
+
+            reclaimed_lamports += self.accounts.vault.lamports();
+
+            CloseAccount {
+                account: self.accounts.vault,
+                destination: self.accounts.vault_rent_recipient,
+                authority: self.accounts.escrow,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke_signed(core::slice::from_ref(&signer))?;
+
+            // Transfer each basket token to the Taker and close its Vault
+            for [mint, vault, taker_ata] in account_groups::<3>(self.accounts.basket) {
+                TransferChecked {
+                    from: vault,
+                    mint,
+                    to: taker_ata,
+                    authority: self.accounts.escrow,
+                    amount: TokenAccountInterface::amount(vault)?,
+                    decimals: MintInterface::decimals(mint)?,
+                    token_program: self.accounts.token_program.key(),
+                }
+                .invoke_signed(core::slice::from_ref(&signer))?;
+
+                if MintInterface::has_extension(mint, TRANSFER_FEE_CONFIG_EXTENSION)? {
+                    HarvestWithheldTokensToMint {
+                        mint,
+                        source: vault,
+                    }
+                    .invoke()?;
+                }
+
+                reclaimed_lamports += vault.lamports();
+
+                CloseAccount {
+                    account: vault,
+                    destination: self.accounts.vault_rent_recipient,
+                    authority: self.accounts.escrow,
+                    token_program: self.accounts.token_program.key(),
+                }
+                .invoke_signed(core::slice::from_ref(&signer))?;
+            }
+        }
+
+        let remaining_receive = base_receive
+            .checked_sub(receive)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Settle the Escrow before paying the Maker so a transfer hook can't re-enter it
+        if is_full_fill {
+            // Close the Escrow
+            reclaimed_lamports += self.accounts.escrow.lamports();
+            ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        } else {
+            // Keep the Escrow open for the remaining amount, scaling down every accepted mint
+            let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+            let stored = Escrow::load_mut(data.as_mut())?;
+            stored.set_receive(
+                escrow.receive - checked_mul_div_ceil(escrow.receive, amount, vault_amount)?,
+            );
+            for (index, option) in escrow.receive_options().iter().enumerate() {
+                stored.set_receive_option(
+                    index,
+                    option.mint,
+                    option.receive - checked_mul_div_ceil(option.receive, amount, vault_amount)?,
+                );
+            }
         }
-        .invoke_signed(&[signer.clone()])?;
-
-        // Transfer from the Taker to the Maker
-        Transfer {
-            from: self.accounts.taker_ata_b,
-            to: self.accounts.maker_ata_b,
-            authority: self.accounts.taker,
-            amount: escrow.receive,
+
+        // Split the protocol fee from the amount owed to the Maker, at the rate fixed at Make so a
+        // later SetFee can't change the terms of an open escrow
+        let (fee, maker_receive) = split_fee(receive, escrow.fee_bps)?;
+
+        if self.native {
+            // Transfer lamports from the Taker to the Maker
+            Transfer {
+                from: self.accounts.taker,
+                to: self.accounts.maker,
+                lamports: maker_receive,
+            }
+            .invoke()?;
+
+            // Transfer the fee from the Taker to the Treasury
+            if fee > 0 {
+                Transfer {
+                    from: self.accounts.taker,
+                    to: self.accounts.treasury_ata_b,
+                    lamports: fee,
+                }
+                .invoke()?;
+            }
+        } else {
+            let decimals_b = MintInterface::decimals(self.accounts.mint_b)?;
+
+            // Transfer from the Taker to the Maker
+            TransferChecked {
+                from: self.accounts.taker_ata_b,
+                mint: self.accounts.mint_b,
+                to: self.accounts.maker_ata_b,
+                authority: self.accounts.taker,
+                amount: maker_receive,
+                decimals: decimals_b,
+                token_program: self.accounts.token_program.key(),
+            }
+            .invoke()?;
+
+            // Transfer the fee from the Taker to the Treasury
+            if fee > 0 {
+                TransferChecked {
+                    from: self.accounts.taker_ata_b,
+                    mint: self.accounts.mint_b,
+                    to: self.accounts.treasury_ata_b,
+                    authority: self.accounts.taker,
+                    amount: fee,
+                    decimals: decimals_b,
+                    token_program: self.accounts.token_program.key(),
+                }
+                .invoke()?;
+            }
         }
-        .invoke()?;
 
-        // Close the Escrow
-        drop(data);
-        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+        // Log the escrow, taker, filled and paid amounts
+        sol_log("Instruction: Take");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(self.accounts.taker.key());
+        sol_log_64(amount, receive, remaining_receive, fee, 0);
+
+        // Summarize the swap for indexers, which is cheaper than reconstructing it from logs
+        let status = if is_full_fill {
+            Self::STATUS_FILLED
+        } else {
+            Self::STATUS_PARTIALLY_FILLED
+        };
+
+        let mut return_data = [0u8; Self::RETURN_DATA_LEN];
+        return_data[0] = Self::RETURN_DATA_VERSION;
+        return_data[1] = status;
+        return_data[2..34].copy_from_slice(&escrow.mint_a);
+        return_data[34..66].copy_from_slice(self.accounts.mint_b.key());
+        return_data[66..74].copy_from_slice(&amount.to_le_bytes());
+        return_data[74..82].copy_from_slice(&receive.to_le_bytes());
+        return_data[82..90].copy_from_slice(&reclaimed_lamports.to_le_bytes());
+
+        set_return_data(&return_data);
 
         Ok(())
     }
@@ -184,22 +652,30 @@ impl<'a> Take<'a> {
 
 #[cfg(test)]
 mod tests {
-    use solana_instruction::{AccountMeta, Instruction};
+    use pinocchio::program_error::ProgramError;
+    use solana_clock::Clock;
+    use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
+    use solana_pubkey::Pubkey;
     use solana_signer::Signer;
+    use solana_transaction_error::TransactionError;
     use spl_associated_token_account::{
         get_associated_token_address_with_program_id,
         solana_program::native_token::LAMPORTS_PER_SOL,
     };
     use spl_token_2022::state::Account as TokenAccount;
 
-    use crate::tests::{
-        constants::{
-            ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
-            TOKEN_PROGRAM_ID,
-        },
-        pda::get_escrow_pda,
-        utils::{
-            build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+    use crate::{
+        Escrow, EscrowError, PinocchioError, Take,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, ESCROW_SEED, MINT_DECIMALS, PROGRAM_ID,
+                SYSTEM_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, freeze_token_account, init_ata,
+                init_mint, init_mint_with_transfer_fee, init_wallet, setup, simulate_expect_error,
+            },
         },
     };
 
@@ -211,6 +687,7 @@ mod tests {
         let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
         let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
         let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
         let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
         let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
 
@@ -219,8 +696,7 @@ mod tests {
         let give_amount: u64 = 500_000_000;
 
         let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
-        let vault =
-            get_associated_token_address_with_program_id(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
 
         let ix = Instruction {
             program_id: PROGRAM_ID,
@@ -234,12 +710,21 @@ mod tests {
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
             ],
             data: [
                 vec![0u8],
+                vec![2u8],
                 seed.to_le_bytes().to_vec(),
                 receive_amount.to_le_bytes().to_vec(),
                 give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
             ]
             .concat(),
         };
@@ -260,6 +745,11 @@ mod tests {
         let pre_maker_ata_b_bal = 0;
         let pre_taker_ata_a_bal = 0;
 
+        // The maker is refunded the rent of both closed accounts
+        let pre_maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+        let escrow_lamports = litesvm.get_account(&escrow_pda).unwrap().lamports;
+        let vault_lamports = litesvm.get_account(&vault).unwrap().lamports;
+
         let ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
@@ -272,9 +762,12 @@ mod tests {
                 AccountMeta::new(taker_ata_a, false),
                 AccountMeta::new(taker_ata_b, false),
                 AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
             ],
             data: [vec![1u8]].concat(),
         };
@@ -296,5 +789,4536 @@ mod tests {
         let post_taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
 
         assert_eq!(pre_taker_ata_a_bal, post_taker_ata_a_bal - give_amount);
+
+        let post_maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+
+        assert_eq!(
+            post_maker_lamports,
+            pre_maker_lamports + escrow_lamports + vault_lamports
+        );
+        assert_eq!(
+            escrow_lamports,
+            litesvm.minimum_balance_for_rent_exemption(Escrow::LEN)
+        );
+    }
+
+    #[test]
+    fn take_with_insufficient_mint_b() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        // The taker is one token short of the amount the maker wants
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), receive_amount - 1);
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[ix],
+            ProgramError::InsufficientFunds,
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_vault_as_taker_ata_a() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // The vault is passed again in place of the taker's ATA A
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[ix],
+            EscrowError::SameTokenAccount.into(),
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_token_2022_mint_as_taker_ata_b() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_2022_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_2022_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+
+        // A Token-2022 mint is shorter than the account type byte of a token account
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(mint_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[ix],
+            ProgramError::InvalidAccountData,
+        );
+    }
+
+    #[test]
+    fn take_with_non_canonical_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Copy the vault to an address that isn't the escrow's ATA
+        let fake_vault = Pubkey::new_unique();
+        let vault_acc = litesvm.get_account(&vault).unwrap();
+        litesvm.set_account(fake_vault, vault_acc).unwrap();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(fake_vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(PinocchioError::InvalidAddress as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_fake_token_program() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let fake_token_program = Pubkey::new_unique();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(fake_token_program, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_empty_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Drain the vault behind the escrow's back
+        init_ata(litesvm, mint_a, escrow_pda, 0);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::EmptyVault as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_frozen_vault() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // The mint's freeze authority freezes the vault mid-escrow
+        freeze_token_account(litesvm, vault);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::VaultFrozen as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_program_owned_maker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // The maker's wallet is handed over to another program mid-escrow
+        let mut maker_acc = litesvm.get_account(&maker.pubkey()).unwrap();
+        maker_acc.owner = TOKEN_PROGRAM_ID;
+        litesvm.set_account(maker.pubkey(), maker_acc).unwrap();
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            core::slice::from_ref(&ix),
+            EscrowError::InvalidMaker.into(),
+        );
+
+        // Once the maker's ATA B exists the escrow can be taken as usual
+        init_ata(litesvm, mint_b, maker.pubkey(), 0);
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            receive_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_closed_maker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // The maker drains their wallet mid-escrow, leaving it closed but still system-owned
+        let mut maker_acc = litesvm.get_account(&maker.pubkey()).unwrap();
+        maker_acc.lamports = 0;
+        litesvm.set_account(maker.pubkey(), maker_acc).unwrap();
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            core::slice::from_ref(&ix),
+            EscrowError::InvalidMaker.into(),
+        );
+
+        // Once the maker's ATA B exists the escrow can be taken as usual
+        init_ata(litesvm, mint_b, maker.pubkey(), 0);
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            receive_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_account_count_mismatch() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), 42);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let accounts = vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(taker_ata_a, false),
+            AccountMeta::new(taker_ata_b, false),
+            AccountMeta::new(maker_ata_b, false),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(treasury_ata_b, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ];
+
+        // Leaving out the config is short of the required accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts[..accounts.len() - 1].to_vec(),
+            data: vec![1u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        );
+
+        // A trailing account that isn't part of a whole basket entry is surplus
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: [
+                accounts,
+                vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)],
+            ]
+            .concat(),
+            data: vec![1u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::TooManyAccountKeys as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn take_with_wrong_mint_taker_ata_a() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // An existing taker ATA for another mint in place of taker_ata_a
+        let taker_ata_a = init_ata(litesvm, mint_c, taker.pubkey(), 0);
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_vesting_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                1_000i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::VestingMismatch as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_transfer_fee_mint() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a =
+            init_mint_with_transfer_fee(litesvm, MINT_DECIMALS, 1_000_000_000, 100, u64::MAX);
+        let mint_b = init_mint(litesvm, TOKEN_2022_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+
+        // The deposit left a 1% transfer fee withheld in the Vault
+        let vault_amount = fetch_account::<TokenAccount>(litesvm, &vault).amount;
+
+        assert_eq!(vault_amount, give_amount - give_amount / 100);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+
+        let vault_acc = litesvm.get_account(&vault);
+
+        assert!(vault_acc.is_none());
+
+        let post_maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(post_maker_ata_b_bal, receive_amount);
+
+        let post_taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
+
+        assert_eq!(post_taker_ata_a_bal, vault_amount - vault_amount / 100);
+    }
+
+    #[test]
+    fn take_with_max_receive_exceeded() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let max_receive: u64 = 90_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![1u8],
+                0u64.to_le_bytes().to_vec(),
+                max_receive.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::SlippageExceeded as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_expected_vault_amount() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let fill_amount: u64 = 100_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let take_ix = |fill_amount: u64, expected_vault_amount: u64| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![1u8],
+                fill_amount.to_le_bytes().to_vec(),
+                0u64.to_le_bytes().to_vec(),
+                expected_vault_amount.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(fill_amount, give_amount)],
+        );
+
+        assert!(result.is_ok());
+
+        // The partial fill leaves the full deposit as a stale expectation
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(0, give_amount)],
+            EscrowError::VaultAmountMismatch.into(),
+        );
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(0, give_amount - fill_amount)],
+        );
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_vault_rent_recipient() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let rent_recipient = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                rent_recipient.pubkey().to_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let vault_rent = litesvm.get_account(&vault).unwrap().lamports;
+        let pre_rent_recipient_bal = litesvm.get_balance(&rent_recipient.pubkey()).unwrap();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(rent_recipient.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let vault_acc = litesvm.get_account(&vault);
+
+        assert!(vault_acc.is_none());
+
+        let post_rent_recipient_bal = litesvm.get_balance(&rent_recipient.pubkey()).unwrap();
+
+        assert_eq!(post_rent_recipient_bal, pre_rent_recipient_bal + vault_rent);
+    }
+
+    #[test]
+    fn take_with_wrong_treasury() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, Pubkey::new_unique(), 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::InvalidTreasury as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn take_twice_in_one_transaction() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        // The second Take must not find the escrow still open
+        let result =
+            build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix.clone(), ix]);
+
+        assert!(result.is_err());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+
+        let vault_amount = fetch_account::<TokenAccount>(litesvm, &vault).amount;
+
+        assert_eq!(vault_amount, give_amount);
+    }
+
+    #[test]
+    fn take_returns_reclaimed_lamports() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let reclaimable_lamports = litesvm.get_account(&vault).unwrap().lamports
+            + litesvm.get_account(&escrow_pda).unwrap().lamports;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        let return_data = result.unwrap().return_data;
+
+        assert_eq!(return_data.program_id, PROGRAM_ID);
+        assert_eq!(
+            u64::from_le_bytes(return_data.data[82..90].try_into().unwrap()),
+            reclaimable_lamports
+        );
+    }
+
+    #[test]
+    fn take_with_existing_maker_ata_b() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = init_ata(litesvm, mint_b, maker.pubkey(), 0);
+
+        let pre_maker_ata_b_bal = 0;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let post_maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(pre_maker_ata_b_bal, post_maker_ata_b_bal - receive_amount);
+    }
+
+    #[test]
+    fn take_with_wrong_mint_maker_ata_b() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Place a token account for another mint at the maker's ATA B address
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 0);
+        let maker_ata_c_acc = litesvm.get_account(&maker_ata_c).unwrap();
+        litesvm.set_account(maker_ata_b, maker_ata_c_acc).unwrap();
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+        );
+    }
+
+    #[test]
+    fn take_with_wrong_mint_b() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let wrong_mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, wrong_mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &wrong_mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(wrong_mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::MintMismatch as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_wrong_mint_a() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let wrong_mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &wrong_mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(wrong_mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::MintMismatch as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_before_start_time() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let start_time = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                start_time.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        clock.unix_timestamp = start_time - 1;
+        litesvm.set_sysvar(&clock);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::NotStarted as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_after_start_time() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let start_time = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                start_time.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        clock.unix_timestamp = start_time;
+        litesvm.set_sysvar(&clock);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn take_before_expiry() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let expiry = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                expiry.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        clock.unix_timestamp = expiry;
+        litesvm.set_sysvar(&clock);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn take_after_expiry() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let mut clock = litesvm.get_sysvar::<Clock>();
+        let expiry = clock.unix_timestamp + 60;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                expiry.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        clock.unix_timestamp = expiry + 1;
+        litesvm.set_sysvar(&clock);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::Expired as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_transfer_fee() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let fee_basis_points: u16 = 100;
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_2022_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint_with_transfer_fee(
+            litesvm,
+            MINT_DECIMALS,
+            1_000_000_000,
+            fee_basis_points,
+            u64::MAX,
+        );
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_2022_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let fee = receive_amount * fee_basis_points as u64 / 10_000;
+        let post_maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(post_maker_ata_b_bal, receive_amount - fee);
+
+        let post_taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
+
+        assert_eq!(post_taker_ata_a_bal, give_amount);
+    }
+
+    #[test]
+    fn take_partial_fill() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let fill_amount = give_amount / 2;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8], fill_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            core::slice::from_ref(&ix),
+        );
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.receive, receive_amount / 2);
+
+        let vault_bal = fetch_account::<TokenAccount>(litesvm, &vault).amount;
+
+        assert_eq!(vault_bal, give_amount - fill_amount);
+
+        let maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(maker_ata_b_bal, receive_amount / 2);
+
+        litesvm.expire_blockhash();
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+
+        let vault_acc = litesvm.get_account(&vault);
+
+        assert!(vault_acc.is_none());
+
+        let post_maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(post_maker_ata_b_bal, receive_amount);
+
+        let post_taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
+
+        assert_eq!(post_taker_ata_a_bal, give_amount);
+    }
+
+    #[test]
+    fn take_partial_fill_preserves_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let pre_escrow_data = litesvm.get_account(&escrow_pda).unwrap().data;
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let fill_amount = give_amount / 5;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8], fill_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let post_escrow_data = litesvm.get_account(&escrow_pda).unwrap().data;
+        let escrow = Escrow::from_bytes(&post_escrow_data).unwrap();
+
+        assert_eq!(escrow.receive, receive_amount - receive_amount / 5);
+
+        // Only the receive amount is written back to the Escrow
+        let receive_offset = core::mem::offset_of!(Escrow, receive);
+
+        assert_eq!(
+            post_escrow_data[..receive_offset],
+            pre_escrow_data[..receive_offset]
+        );
+        assert_eq!(
+            post_escrow_data[receive_offset + 8..],
+            pre_escrow_data[receive_offset + 8..]
+        );
+    }
+
+    #[test]
+    fn take_partial_fill_rounds_up() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let pre_taker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_b).amount;
+
+        // A single unit of mint A is worth a fraction of a unit of mint B
+        let fill_amount = 1u64;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8], fill_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // The taker pays a whole unit rather than nothing
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_b).amount,
+            pre_taker_ata_b_bal - 1
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            fill_amount
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.receive, receive_amount - 1);
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount - fill_amount
+        );
+    }
+
+    #[test]
+    fn take_basket() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let give_amount_c: u64 = 250_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault_c = get_vault_address(&escrow_pda, &mint_c, &TOKEN_PROGRAM_ID);
+
+        // Mint C, the maker's ATA and the escrow's vault for it follow the fixed accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(maker_ata_c, false),
+                AccountMeta::new(vault_c, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                give_amount_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let taker_ata_c = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_c,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(vault_c, false),
+                AccountMeta::new(taker_ata_c, false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+
+        let vault_c_acc = litesvm.get_account(&vault_c);
+
+        assert!(vault_c_acc.is_none());
+
+        let taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
+        let taker_ata_c_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_c).amount;
+
+        assert_eq!(taker_ata_a_bal, give_amount);
+        assert_eq!(taker_ata_c_bal, give_amount_c);
+
+        let maker_ata_b_bal = fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount;
+
+        assert_eq!(maker_ata_b_bal, receive_amount);
+    }
+
+    #[test]
+    fn take_basket_partial_fill() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let maker_ata_c = init_ata(litesvm, mint_c, maker.pubkey(), 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let give_amount_c: u64 = 250_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+        let vault_c = get_vault_address(&escrow_pda, &mint_c, &TOKEN_PROGRAM_ID);
+
+        // Mint C, the maker's ATA and the escrow's vault for it follow the fixed accounts
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(maker_ata_c, false),
+                AccountMeta::new(vault_c, false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                give_amount_c.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let taker_ata_c = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_c,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let fill_amount = give_amount / 2;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+                AccountMeta::new_readonly(mint_c, false),
+                AccountMeta::new(vault_c, false),
+                AccountMeta::new(taker_ata_c, false),
+            ],
+            data: [vec![1u8], fill_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::BasketMismatch as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn take_own_escrow() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let maker_ata_b = init_ata(litesvm, mint_b, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_wrong_maker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let wrong_maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let wrong_maker_ata_b = get_associated_token_address_with_program_id(
+            &wrong_maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(wrong_maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(wrong_maker_ata_b, false),
+                AccountMeta::new(wrong_maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::MakerMismatch as u32)
+            )
+        );
+
+        let wrong_maker_ata_b_acc = litesvm.get_account(&wrong_maker_ata_b);
+
+        assert!(wrong_maker_ata_b_acc.is_none());
+    }
+
+    #[test]
+    fn take_with_authorized_taker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let unauthorized_taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                taker.pubkey().to_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        for (signer, expected_ok) in [(&unauthorized_taker, false), (&taker, true)] {
+            let signer_ata_a = get_associated_token_address_with_program_id(
+                &signer.pubkey(),
+                &mint_a,
+                &TOKEN_PROGRAM_ID,
+            );
+            let signer_ata_b = init_ata(litesvm, mint_b, signer.pubkey(), 1_000_000_000);
+
+            let ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(signer.pubkey(), true),
+                    AccountMeta::new(maker.pubkey(), false),
+                    AccountMeta::new(escrow_pda, false),
+                    AccountMeta::new_readonly(mint_a, false),
+                    AccountMeta::new_readonly(mint_b, false),
+                    AccountMeta::new(vault, false),
+                    AccountMeta::new(signer_ata_a, false),
+                    AccountMeta::new(signer_ata_b, false),
+                    AccountMeta::new(maker_ata_b, false),
+                    AccountMeta::new(maker.pubkey(), false),
+                    AccountMeta::new(treasury_ata_b, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(get_config_pda(), false),
+                ],
+                data: [vec![1u8]].concat(),
+            };
+
+            let result = build_and_send_transaction(litesvm, &[signer], &signer.pubkey(), &[ix]);
+
+            if expected_ok {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(
+                    result.unwrap_err().err,
+                    TransactionError::InstructionError(
+                        0,
+                        InstructionError::Custom(EscrowError::UnauthorizedTaker as u32)
+                    )
+                );
+            }
+        }
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_none());
+    }
+
+    #[test]
+    fn take_with_invalid_escrow_pda() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Tamper with the stored seed so the PDA no longer matches
+        let mut escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let bump = escrow_acc.data[Escrow::LEN - 1];
+        let tampered_seed = (seed + 1..)
+            .find(|tampered_seed| {
+                Pubkey::create_program_address(
+                    &[
+                        ESCROW_SEED,
+                        maker.pubkey().as_ref(),
+                        &tampered_seed.to_le_bytes(),
+                        &[bump],
+                    ],
+                    &PROGRAM_ID,
+                )
+                .is_ok()
+            })
+            .unwrap();
+        escrow_acc.data[8..16].copy_from_slice(&tampered_seed.to_le_bytes());
+        litesvm.set_account(escrow_pda, escrow_acc).unwrap();
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::InvalidEscrowPda as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_tampered_bump() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Tamper with the stored bump so the seeds no longer produce an off-curve address
+        let mut escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let tampered_bump = (0..=u8::MAX)
+            .find(|tampered_bump| {
+                Pubkey::create_program_address(
+                    &[
+                        ESCROW_SEED,
+                        maker.pubkey().as_ref(),
+                        &seed.to_le_bytes(),
+                        &[*tampered_bump],
+                    ],
+                    &PROGRAM_ID,
+                )
+                .is_err()
+            })
+            .unwrap();
+        escrow_acc.data[Escrow::LEN - 1] = tampered_bump;
+        litesvm.set_account(escrow_pda, escrow_acc).unwrap();
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(EscrowError::InvalidEscrowPda as u32)
+            )
+        );
+
+        let escrow_acc = litesvm.get_account(&escrow_pda);
+
+        assert!(escrow_acc.is_some());
+    }
+
+    #[test]
+    fn take_with_invalid_escrow_length() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // Append a trailing byte so the escrow no longer matches Escrow::LEN
+        let mut escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        escrow_acc.data.push(0);
+        litesvm.set_account(escrow_pda, escrow_acc).unwrap();
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8]].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn take_with_receive_option() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_c = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_d = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_d = init_ata(litesvm, mint_d, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_d = init_ata(litesvm, mint_d, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+        let receive_c: u64 = 200_000_000;
+        let receive_d: u64 = 300_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        // The maker also accepts mint C and mint D, each at its own amount
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+            ],
+            data: [
+                vec![18u8],
+                mint_c.to_bytes().to_vec(),
+                receive_c.to_le_bytes().to_vec(),
+                mint_d.to_bytes().to_vec(),
+                receive_d.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.receive_for(&mint_c.to_bytes()), Some(receive_c));
+        assert_eq!(escrow.receive_for(&mint_d.to_bytes()), Some(receive_d));
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_d = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_d,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // The taker pays with the second option instead of mint B
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_d, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_d, false),
+                AccountMeta::new(maker_ata_d, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_d, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert!(litesvm.get_account(&vault).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_d).amount,
+            receive_d
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_swapped_vault_and_taker_ata() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_a = init_ata(litesvm, mint_a, taker.pubkey(), 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Both accounts hold mint A, so only their addresses tell them apart
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[ix],
+            PinocchioError::InvalidAddress.into(),
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_with_mismatched_token_program() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_2022_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_a = init_ata(litesvm, mint_a, taker.pubkey(), 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let take_ix = |token_program: Pubkey| Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        // The vault belongs to token-2022 while the taker's mint B account is classic
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(TOKEN_PROGRAM_ID)],
+            ProgramError::IncorrectProgramId,
+        );
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[take_ix(TOKEN_2022_PROGRAM_ID)],
+            ProgramError::IncorrectProgramId,
+        );
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &vault).amount,
+            give_amount
+        );
+    }
+
+    #[test]
+    fn take_return_data() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let fill_amount = give_amount / 2;
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [vec![1u8], fill_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        let return_data = result.unwrap().return_data;
+
+        assert_eq!(return_data.program_id, PROGRAM_ID);
+
+        // The swap summary decodes at fixed offsets behind its version byte
+        let data = return_data.data;
+
+        assert_eq!(data.len(), Take::RETURN_DATA_LEN);
+        assert_eq!(data[0], Take::RETURN_DATA_VERSION);
+        assert_eq!(data[1], Take::STATUS_PARTIALLY_FILLED);
+        assert_eq!(Pubkey::try_from(&data[2..34]).unwrap(), mint_a);
+        assert_eq!(Pubkey::try_from(&data[34..66]).unwrap(), mint_b);
+        assert_eq!(
+            u64::from_le_bytes(data[66..74].try_into().unwrap()),
+            fill_amount
+        );
+        assert_eq!(
+            u64::from_le_bytes(data[74..82].try_into().unwrap()),
+            receive_amount / 2
+        );
+        assert_eq!(u64::from_le_bytes(data[82..90].try_into().unwrap()), 0);
     }
 }
@@ -0,0 +1,315 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::{sol_log, sol_log_64},
+    program_error::ProgramError,
+    pubkey,
+};
+
+use crate::{
+    AccountCheck, AssociatedTokenProgram, ConfigAccount, MintInterface, SignerAccount,
+    SystemProgram, Take, TokenProgramInterface,
+};
+
+pub struct TakeManyAccounts<'a> {
+    pub taker: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub mint_b: &'a AccountInfo,
+    pub taker_ata_a: &'a AccountInfo,
+    pub taker_ata_b: &'a AccountInfo,
+    pub treasury_ata_b: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_account_program: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TakeManyAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            taker,
+            mint_a,
+            mint_b,
+            taker_ata_a,
+            taker_ata_b,
+            treasury_ata_b,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+            remaining_accounts @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        ConfigAccount::check(config)?;
+        SignerAccount::check(taker)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+
+        // Return the accounts
+        Ok(Self {
+            taker,
+            mint_a,
+            mint_b,
+            taker_ata_a,
+            taker_ata_b,
+            treasury_ata_b,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+            remaining_accounts,
+        })
+    }
+}
+
+pub struct TakeManyInstructionData {
+    pub count: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for TakeManyInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u8>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let count = data[0];
+
+        // Instruction Checks
+        if count == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { count })
+    }
+}
+
+pub struct TakeMany<'a> {
+    pub accounts: TakeManyAccounts<'a>,
+    pub instruction_data: TakeManyInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for TakeMany<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = TakeManyAccounts::try_from(accounts)?;
+        let instruction_data = TakeManyInstructionData::try_from(data)?;
+
+        // Check if there are exactly enough accounts for every escrow
+        if accounts.remaining_accounts.len()
+            != instruction_data.count as usize * TakeMany::ACCOUNTS_PER_ESCROW
+        {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TakeMany<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    // Maker, escrow, vault, maker ATA B and vault rent recipient
+    pub const ACCOUNTS_PER_ESCROW: usize = 5;
+
+    pub fn process(&self) -> ProgramResult {
+        for escrow_accounts in self
+            .accounts
+            .remaining_accounts
+            .chunks_exact(Self::ACCOUNTS_PER_ESCROW)
+        {
+            let [maker, escrow, vault, maker_ata_b, vault_rent_recipient] = escrow_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            // Fill each escrow in full with the same checks as Take
+            let accounts = [
+                *self.accounts.taker,
+                *maker,
+                *escrow,
+                *self.accounts.mint_a,
+                *self.accounts.mint_b,
+                *vault,
+                *self.accounts.taker_ata_a,
+                *self.accounts.taker_ata_b,
+                *maker_ata_b,
+                *vault_rent_recipient,
+                *self.accounts.treasury_ata_b,
+                *self.accounts.system_program,
+                *self.accounts.token_program,
+                *self.accounts.associated_token_account_program,
+                *self.accounts.config,
+            ];
+
+            Take::try_from((&[][..], &accounts[..]))?.process()?;
+        }
+
+        // Log the taker and number of escrows filled
+        sol_log("Instruction: TakeMany");
+        pubkey::log(self.accounts.taker.key());
+        sol_log_64(self.instruction_data.count as u64, 0, 0, 0, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::tests::{
+        constants::{
+            ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+            TOKEN_PROGRAM_ID, TREASURY,
+        },
+        pda::{get_config_pda, get_escrow_pda, get_vault_address},
+        utils::{
+            build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+        },
+    };
+
+    #[test]
+    fn take_many() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let makers: Vec<Keypair> = (0..2)
+            .map(|_| init_wallet(litesvm, LAMPORTS_PER_SOL))
+            .collect();
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let mut remaining_accounts = vec![];
+
+        for maker in makers.iter() {
+            let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+            let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+            let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+            let maker_ata_b = get_associated_token_address_with_program_id(
+                &maker.pubkey(),
+                &mint_b,
+                &TOKEN_PROGRAM_ID,
+            );
+
+            let ix = Instruction {
+                program_id: PROGRAM_ID,
+                accounts: vec![
+                    AccountMeta::new(maker.pubkey(), true),
+                    AccountMeta::new(escrow_pda, false),
+                    AccountMeta::new_readonly(mint_a, false),
+                    AccountMeta::new_readonly(mint_b, false),
+                    AccountMeta::new(maker_ata_a, false),
+                    AccountMeta::new(vault, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(get_config_pda(), false),
+                ],
+                data: [
+                    vec![0u8],
+                    vec![2u8],
+                    seed.to_le_bytes().to_vec(),
+                    receive_amount.to_le_bytes().to_vec(),
+                    give_amount.to_le_bytes().to_vec(),
+                    0i64.to_le_bytes().to_vec(),
+                    0i64.to_le_bytes().to_vec(),
+                    0i64.to_le_bytes().to_vec(),
+                    0i64.to_le_bytes().to_vec(),
+                    [0u8; 32].to_vec(),
+                    [0u8; 32].to_vec(),
+                    [0u8; 32].to_vec(),
+                ]
+                .concat(),
+            };
+
+            let _ = build_and_send_transaction(litesvm, &[maker], &maker.pubkey(), &[ix]);
+
+            remaining_accounts.extend([
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+            ]);
+        }
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: [
+                vec![
+                    AccountMeta::new(taker.pubkey(), true),
+                    AccountMeta::new_readonly(mint_a, false),
+                    AccountMeta::new_readonly(mint_b, false),
+                    AccountMeta::new(taker_ata_a, false),
+                    AccountMeta::new(taker_ata_b, false),
+                    AccountMeta::new(treasury_ata_b, false),
+                    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                    AccountMeta::new_readonly(get_config_pda(), false),
+                ],
+                remaining_accounts,
+            ]
+            .concat(),
+            data: vec![5u8, makers.len() as u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        for maker in makers.iter() {
+            let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+            let maker_ata_b = get_associated_token_address_with_program_id(
+                &maker.pubkey(),
+                &mint_b,
+                &TOKEN_PROGRAM_ID,
+            );
+
+            assert!(litesvm.get_account(&escrow_pda).is_none());
+            assert_eq!(
+                fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+                receive_amount
+            );
+        }
+
+        let post_taker_ata_a_bal = fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount;
+
+        assert_eq!(post_taker_ata_a_bal, give_amount * makers.len() as u64);
+    }
+}
@@ -0,0 +1,260 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    AccountCheck, AssociatedTokenProgram, ConfigAccount, EscrowError, MintInterface,
+    ProgramAccount, SignerAccount, SystemProgram, Take, TakeAccounts, TakeInstructionData,
+    TokenProgramInterface,
+};
+
+pub struct TakeNative<'a> {
+    pub take: Take<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for TakeNative<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // Same as the Take accounts without the ATAs for mint B, the treasury is its wallet
+        let [
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            taker_ata_a,
+            vault_rent_recipient,
+            treasury,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+            basket @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // A mint, vault and taker ATA follow for each basket entry, anything left over is surplus
+        if basket.len() % 3 != 0 {
+            return Err(EscrowError::TooManyAccountKeys.into());
+        }
+
+        // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgramInterface::check(token_program)?;
+        AssociatedTokenProgram::check(associated_token_account_program)?;
+        ConfigAccount::check(config)?;
+        SignerAccount::check(taker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        MintInterface::check(mint_b)?;
+
+        let accounts = TakeAccounts {
+            taker,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            vault,
+            recipient: taker,
+            taker_ata_a,
+            // Lamports move between the wallets themselves
+            taker_ata_b: taker,
+            maker_ata_b: maker,
+            vault_rent_recipient,
+            treasury_ata_b: treasury,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            config,
+            basket,
+        };
+
+        let mut take = Take::new(accounts, TakeInstructionData::try_from(data)?)?;
+        take.native = true;
+
+        Ok(Self { take })
+    }
+}
+
+impl<'a> TakeNative<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    // Only for escrows asking for the native mint, the taker pays the maker in lamports
+    pub fn process(&mut self) -> ProgramResult {
+        self.take.process()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::{
+        Escrow, EscrowError, FEE_BPS, split_fee,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID, TREASURY,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{
+                build_and_send_transaction, fetch_account, init_ata, init_mint, init_native_mint,
+                init_wallet, setup, simulate_expect_error,
+            },
+        },
+    };
+
+    #[test]
+    fn take_native() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, 2 * LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_native_mint(litesvm);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = LAMPORTS_PER_SOL / 2;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        assert!(
+            Escrow::load(escrow_acc.data.as_ref())
+                .unwrap()
+                .receive_native
+        );
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        // Take pays in mint B, which the escrow doesn't accept
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), receive_amount);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_b = init_ata(litesvm, mint_b, maker.pubkey(), 0);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![1u8],
+        };
+
+        simulate_expect_error(
+            litesvm,
+            &[&taker],
+            &taker.pubkey(),
+            &[ix],
+            EscrowError::ReceiveAssetMismatch.into(),
+        );
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(taker_ata_a, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(TREASURY, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: vec![15u8],
+        };
+
+        // The escrow and vault rent are returned to the maker alongside the payment
+        let maker_lamports = litesvm.get_account(&maker.pubkey()).unwrap().lamports;
+        let reclaimed_lamports = litesvm.get_account(&escrow_pda).unwrap().lamports
+            + litesvm.get_account(&vault).unwrap().lamports;
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let (_, maker_receive) = split_fee(receive_amount, FEE_BPS).unwrap();
+
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert_eq!(
+            litesvm.get_account(&maker.pubkey()).unwrap().lamports,
+            maker_lamports + maker_receive + reclaimed_lamports
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_a).amount,
+            give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_b).amount,
+            receive_amount
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{Take, TakeAccounts, TakeInstructionData};
+
+pub struct TakeTo<'a> {
+    pub take: Take<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for TakeTo<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // The recipient comes first, followed by the Take accounts with its ATA as taker_ata_a
+        let [recipient, accounts @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let mut accounts = TakeAccounts::try_from(accounts)?;
+        accounts.recipient = recipient;
+        let instruction_data = TakeInstructionData::try_from(data)?;
+
+        Ok(Self {
+            take: Take::new(accounts, instruction_data)?,
+        })
+    }
+}
+
+impl<'a> TakeTo<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    // The taker still signs and pays mint B, only mint A is delivered to the recipient
+    pub fn process(&mut self) -> ProgramResult {
+        self.take.process()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use crate::tests::{
+        constants::{
+            ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+            TOKEN_PROGRAM_ID, TREASURY,
+        },
+        pda::{get_config_pda, get_escrow_pda, get_vault_address},
+        utils::{
+            build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+        },
+    };
+
+    #[test]
+    fn take_to() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let recipient = Keypair::new();
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let taker_ata_a = get_associated_token_address_with_program_id(
+            &taker.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let recipient_ata_a = get_associated_token_address_with_program_id(
+            &recipient.pubkey(),
+            &mint_a,
+            &TOKEN_PROGRAM_ID,
+        );
+        let maker_ata_b = get_associated_token_address_with_program_id(
+            &maker.pubkey(),
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let accounts = |recipient_ata_a| {
+            vec![
+                AccountMeta::new_readonly(recipient.pubkey(), false),
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new(recipient_ata_a, false),
+                AccountMeta::new(taker_ata_b, false),
+                AccountMeta::new(maker_ata_b, false),
+                AccountMeta::new(maker.pubkey(), false),
+                AccountMeta::new(treasury_ata_b, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ]
+        };
+
+        // The taker's own ATA is rejected in place of the recipient's
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts(taker_ata_a),
+            data: vec![10u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_err());
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts(recipient_ata_a),
+            data: vec![10u8],
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        assert!(litesvm.get_account(&escrow_pda).is_none());
+        assert!(litesvm.get_account(&taker_ata_a).is_none());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &recipient_ata_a).amount,
+            give_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &taker_ata_b).amount,
+            1_000_000_000 - receive_amount
+        );
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            receive_amount
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use core::mem::size_of;
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    log::sol_log,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey, create_program_address},
+};
+
+use crate::{
+    AccountCheck, ESCROW_SEED_PREFIX, Escrow, EscrowError, ProgramAccount, SignerAccount,
+    assert_is_maker, read_bytes,
+};
+
+pub struct TransferMakerAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferMakerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+
+        // Return the accounts
+        Ok(Self { maker, escrow })
+    }
+}
+
+pub struct TransferMakerInstructionData {
+    pub new_maker: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for TransferMakerInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let new_maker: Pubkey = read_bytes(data, 0)?;
+
+        // Instruction Checks
+        if new_maker == [0u8; 32] {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { new_maker })
+    }
+}
+
+pub struct TransferMaker<'a> {
+    pub accounts: TransferMakerAccounts<'a>,
+    pub instruction_data: TransferMakerInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for TransferMaker<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = TransferMakerAccounts::try_from(accounts)?;
+        let instruction_data = TransferMakerInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TransferMaker<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    // The escrow keeps its address, which stays derived from the creator rather than the maker
+    pub fn process(&self) -> ProgramResult {
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+
+        // Check if the maker signed for the escrow
+        assert_is_maker(escrow, self.accounts.maker)?;
+
+        // Check if the escrow is valid
+        let escrow_key = create_program_address(
+            &[
+                ESCROW_SEED_PREFIX,
+                &escrow.creator,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != self.accounts.escrow.key() {
+            return Err(EscrowError::InvalidEscrowPda.into());
+        }
+
+        // Vault rent follows the maker unless another recipient was set
+        if escrow.vault_rent_recipient.eq(self.accounts.maker.key()) {
+            escrow.set_vault_rent_recipient(self.instruction_data.new_maker);
+        }
+
+        escrow.set_maker(self.instruction_data.new_maker);
+
+        // Log the escrow and new maker
+        sol_log("Instruction: TransferMaker");
+        pubkey::log(self.accounts.escrow.key());
+        pubkey::log(&self.instruction_data.new_maker);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_signer::Signer;
+    use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+    use crate::{
+        Escrow,
+        tests::{
+            constants::{
+                ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+                TOKEN_PROGRAM_ID,
+            },
+            pda::{get_config_pda, get_escrow_pda, get_vault_address},
+            utils::{build_and_send_transaction, init_ata, init_mint, init_wallet, setup},
+        },
+    };
+
+    #[test]
+    fn transfer_maker() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let new_maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let new_receive_amount: u64 = 150_000_000;
+        let give_amount: u64 = 500_000_000;
+        let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+        let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new_readonly(mint_b, false),
+                AccountMeta::new(maker_ata_a, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(get_config_pda(), false),
+            ],
+            data: [
+                vec![0u8],
+                vec![2u8],
+                seed.to_le_bytes().to_vec(),
+                receive_amount.to_le_bytes().to_vec(),
+                give_amount.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                0i64.to_le_bytes().to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+                [0u8; 32].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let _ = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+            ],
+            data: [vec![9u8], new_maker.pubkey().to_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&escrow_pda).unwrap();
+        let escrow = Escrow::load(escrow_acc.data.as_ref()).unwrap();
+
+        assert_eq!(escrow.maker, new_maker.pubkey().to_bytes());
+        assert_eq!(escrow.creator, maker.pubkey().to_bytes());
+        assert_eq!(escrow.vault_rent_recipient, new_maker.pubkey().to_bytes());
+
+        // The new maker can manage the escrow at its original address
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(new_maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+            ],
+            data: [vec![4u8], new_receive_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&new_maker], &new_maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        // The old maker no longer can
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(maker.pubkey(), true),
+                AccountMeta::new(escrow_pda, false),
+            ],
+            data: [vec![4u8], receive_amount.to_le_bytes().to_vec()].concat(),
+        };
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_err());
+    }
+}
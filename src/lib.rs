@@ -3,20 +3,54 @@ use pinocchio::{
     pubkey::Pubkey,
 };
 
+// Programs that depend on this crate for the cpi module build it with no-entrypoint
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
 pub mod instructions;
 pub use instructions::*;
 
+pub mod instruction;
+pub use instruction::*;
+
 pub mod state;
 pub use state::*;
 
 pub mod errors;
 pub use errors::*;
 
+pub mod math;
+pub use math::*;
+
+pub mod constants;
+pub use constants::*;
+
+pub mod cpi;
+
+#[cfg(feature = "client")]
+pub mod sdk;
+
 pub mod tests;
 
-// 22222222222222222222222222222222222222222222
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("the devnet and mainnet features select different program ids, enable only one");
+
+// Gegn5GsVipTyfF8tkkvmz3fVTXW2DDmKPkK5brHewE3z
+#[cfg(feature = "devnet")]
+pub const ID: Pubkey = [
+    0xe8, 0x86, 0xc5, 0x1d, 0x49, 0x73, 0xcc, 0x23, 0xc8, 0xf2, 0xf9, 0x55, 0xfb, 0x69, 0x2c, 0x1e,
+    0xda, 0xa1, 0x1d, 0x03, 0x29, 0x93, 0x8a, 0xc2, 0xfb, 0x83, 0x22, 0x83, 0x7e, 0x09, 0x78, 0x81,
+];
+
+// GA79r9t7DxFHcDCnXfA6a8PFoag5no2kJxgzGdriDJtJ
+#[cfg(feature = "mainnet")]
+pub const ID: Pubkey = [
+    0xe1, 0x34, 0x7c, 0xe5, 0xa3, 0x4a, 0xc9, 0xb0, 0xf3, 0xdb, 0x8c, 0x63, 0x01, 0xdf, 0x54, 0xb5,
+    0x8c, 0xf5, 0x7e, 0x5a, 0xd6, 0x46, 0x9c, 0x0b, 0x52, 0x51, 0xdd, 0x73, 0x13, 0x7a, 0xa1, 0x93,
+];
+
+// Local validator and tests, 22222222222222222222222222222222222222222222
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
     0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
@@ -29,8 +63,54 @@ fn process_instruction(
 ) -> ProgramResult {
     match instruction_data.split_first() {
         Some((Make::DISCRIMINATOR, data)) => Make::try_from((data, accounts))?.process(),
-        Some((Take::DISCRIMINATOR, _)) => Take::try_from(accounts)?.process(),
+        Some((Take::DISCRIMINATOR, data)) => Take::try_from((data, accounts))?.process(),
         Some((Refund::DISCRIMINATOR, _)) => Refund::try_from(accounts)?.process(),
+        Some((TopUp::DISCRIMINATOR, data)) => TopUp::try_from((data, accounts))?.process(),
+        Some((UpdateReceive::DISCRIMINATOR, data)) => {
+            UpdateReceive::try_from((data, accounts))?.process()
+        }
+        Some((TakeMany::DISCRIMINATOR, data)) => TakeMany::try_from((data, accounts))?.process(),
+        Some((QueryEscrow::DISCRIMINATOR, _)) => QueryEscrow::try_from(accounts)?.process(),
+        Some((Amend::DISCRIMINATOR, data)) => Amend::try_from((data, accounts))?.process(),
+        Some((Claim::DISCRIMINATOR, _)) => Claim::try_from(accounts)?.process(),
+        Some((TransferMaker::DISCRIMINATOR, data)) => {
+            TransferMaker::try_from((data, accounts))?.process()
+        }
+        Some((TakeTo::DISCRIMINATOR, data)) => TakeTo::try_from((data, accounts))?.process(),
+        Some((Split::DISCRIMINATOR, data)) => Split::try_from((data, accounts))?.process(),
+        Some((MakeDelegated::DISCRIMINATOR, data)) => {
+            MakeDelegated::try_from((data, accounts))?.process()
+        }
+        Some((InitConfig::DISCRIMINATOR, _)) => InitConfig::try_from(accounts)?.process(),
+        Some((SetPaused::DISCRIMINATOR, data)) => SetPaused::try_from((data, accounts))?.process(),
+        Some((TakeNative::DISCRIMINATOR, data)) => {
+            TakeNative::try_from((data, accounts))?.process()
+        }
+        Some((Reap::DISCRIMINATOR, _)) => Reap::try_from(accounts)?.process(),
+        Some((CloseEscrow::DISCRIMINATOR, _)) => CloseEscrow::try_from(accounts)?.process(),
+        Some((SetReceiveOptions::DISCRIMINATOR, data)) => {
+            SetReceiveOptions::try_from((data, accounts))?.process()
+        }
+        Some((MigrateEscrow::DISCRIMINATOR, _)) => MigrateEscrow::try_from(accounts)?.process(),
+        Some((CommitTake::DISCRIMINATOR, data)) => {
+            CommitTake::try_from((data, accounts))?.process()
+        }
+        Some((RevealTake::DISCRIMINATOR, data)) => {
+            RevealTake::try_from((data, accounts))?.process()
+        }
+        Some((ExtendExpiry::DISCRIMINATOR, data)) => {
+            ExtendExpiry::try_from((data, accounts))?.process()
+        }
+        Some((SetFee::DISCRIMINATOR, data)) => SetFee::try_from((data, accounts))?.process(),
+        Some((SetFeeRecipient::DISCRIMINATOR, data)) => {
+            SetFeeRecipient::try_from((data, accounts))?.process()
+        }
+        Some((MakeAndTake::DISCRIMINATOR, data)) => {
+            MakeAndTake::try_from((data, accounts))?.process()
+        }
+        Some((SetMinDeposit::DISCRIMINATOR, data)) => {
+            SetMinDeposit::try_from((data, accounts))?.process()
+        }
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
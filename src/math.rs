@@ -0,0 +1,111 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::MAX_BPS;
+
+#[inline(always)]
+pub fn checked_mul_div(a: u64, b: u64, c: u64) -> Result<u64, ProgramError> {
+    (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|product| product.checked_div(c as u128))
+        .and_then(|quotient| u64::try_from(quotient).ok())
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+// Same as checked_mul_div, rounding up instead of down
+#[inline(always)]
+pub fn checked_mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64, ProgramError> {
+    (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|product| product.checked_add((c as u128).checked_sub(1)?))
+        .and_then(|product| product.checked_div(c as u128))
+        .and_then(|quotient| u64::try_from(quotient).ok())
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+// Splits an amount into the fee and the remainder, rounding the fee down so the
+// remainder never loses out
+#[inline(always)]
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64), ProgramError> {
+    if fee_bps > MAX_BPS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let fee = checked_mul_div(amount, fee_bps as u64, MAX_BPS as u64)?;
+
+    Ok((fee, amount - fee))
+}
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+
+    use crate::{checked_mul_div, checked_mul_div_ceil, split_fee};
+
+    #[test]
+    fn mul_div() {
+        assert_eq!(
+            checked_mul_div(100_000_000, 250_000_000, 500_000_000),
+            Ok(50_000_000)
+        );
+        assert_eq!(checked_mul_div(u64::MAX, u64::MAX, u64::MAX), Ok(u64::MAX));
+        assert_eq!(checked_mul_div(7, 1, 2), Ok(3));
+    }
+
+    #[test]
+    fn mul_div_overflow() {
+        assert_eq!(
+            checked_mul_div(u64::MAX, 2, 1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn mul_div_by_zero() {
+        assert_eq!(
+            checked_mul_div(100, 100, 0),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn mul_div_ceil() {
+        assert_eq!(checked_mul_div_ceil(7, 1, 2), Ok(4));
+        assert_eq!(checked_mul_div_ceil(8, 1, 2), Ok(4));
+        assert_eq!(checked_mul_div_ceil(100_000_000, 1, 500_000_000), Ok(1));
+        assert_eq!(checked_mul_div_ceil(0, 1, 2), Ok(0));
+        assert_eq!(
+            checked_mul_div_ceil(u64::MAX, u64::MAX, u64::MAX),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            checked_mul_div_ceil(100, 100, 0),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn split_fee_zero_bps() {
+        assert_eq!(split_fee(100_000_000, 0), Ok((0, 100_000_000)));
+    }
+
+    #[test]
+    fn split_fee_fifty_bps() {
+        assert_eq!(split_fee(100_000_000, 50), Ok((500_000, 99_500_000)));
+    }
+
+    #[test]
+    fn split_fee_rounds_down() {
+        // 199 * 50 / 10_000 is 0.995, the maker keeps the fraction
+        assert_eq!(split_fee(199, 50), Ok((0, 199)));
+        assert_eq!(split_fee(201, 50), Ok((1, 200)));
+        assert_eq!(
+            split_fee(u64::MAX, 50),
+            Ok((u64::MAX / 200, u64::MAX - u64::MAX / 200))
+        );
+    }
+
+    #[test]
+    fn split_fee_invalid_bps() {
+        assert_eq!(split_fee(100, 10_001), Err(ProgramError::InvalidArgument));
+    }
+}
@@ -0,0 +1,1020 @@
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+use crate::{
+    AmendInstructionData, BPF_LOADER_UPGRADEABLE_ID, COMMITMENT_SEED, CONFIG_SEED,
+    CommitTakeInstructionData, ESCROW_MAKER_OFFSET, ESCROW_SEED_PREFIX, Escrow,
+    ExtendExpiryInstructionData, MAX_BASKET_ENTRIES, MakeAndTake, MakeInstructionData, NATIVE_MINT,
+    Reap, ReceiveOption, ReceiveOptions, RevealTakeInstructionData, SetFeeInstructionData,
+    SetFeeRecipientInstructionData, SetMinDepositInstructionData, SetPausedInstructionData,
+    SetReceiveOptionsInstructionData, SplitInstructionData, TREASURY, TakeInstructionData,
+    TakeManyInstructionData, TakeNative, TakeTo, TopUpInstructionData,
+    TransferMakerInstructionData, UpdateReceiveInstructionData,
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array(pinocchio_system::ID);
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(pinocchio_associated_token_account::ID);
+pub const TREASURY_ID: Pubkey = Pubkey::new_from_array(TREASURY);
+pub const NATIVE_MINT_ID: Pubkey = Pubkey::new_from_array(NATIVE_MINT);
+pub const BPF_LOADER_UPGRADEABLE_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(BPF_LOADER_UPGRADEABLE_ID);
+
+pub fn find_escrow_address(maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    find_escrow_address_with_prefix(ESCROW_SEED_PREFIX, maker, seed)
+}
+
+// For deployments built with a different ESCROW_SEED_PREFIX
+pub fn find_escrow_address_with_prefix(prefix: &[u8], maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[prefix, maker.as_ref(), &seed.to_le_bytes()], &PROGRAM_ID)
+}
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &PROGRAM_ID)
+}
+
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE_PROGRAM_ID)
+}
+
+pub fn find_commitment_address(escrow: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMITMENT_SEED, escrow.as_ref()], &PROGRAM_ID)
+}
+
+pub fn get_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+// The vault is the escrow's ATA for mint A
+pub fn get_vault_address(escrow: &Pubkey, mint_a: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address(escrow, mint_a, token_program)
+}
+
+// getProgramAccounts filters listing the escrows a maker currently holds, which follow the
+// maker across TransferMaker unlike the addresses derived from the creator
+pub fn maker_escrow_filters(maker: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize(Escrow::LEN as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &Escrow::DISCRIMINATOR)),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            ESCROW_MAKER_OFFSET,
+            maker.as_ref(),
+        )),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+    expiry: i64,
+    start_time: i64,
+    vesting_start: i64,
+    vesting_duration: i64,
+    authorized_taker: &Pubkey,
+    vault_rent_recipient: &Pubkey,
+    memo: &[u8; 32],
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_address().0, false),
+        ],
+        data: crate::Instruction::Make(MakeInstructionData {
+            seed,
+            receive,
+            amount,
+            expiry,
+            start_time,
+            vesting_start,
+            vesting_duration,
+            authorized_taker: authorized_taker.to_bytes(),
+            vault_rent_recipient: vault_rent_recipient.to_bytes(),
+            memo: *memo,
+            basket_amounts: [0; MAX_BASKET_ENTRIES],
+            basket_len: 0,
+        })
+        .pack(),
+    }
+}
+
+// A zero max_receive disables the slippage check and a zero expected_vault_amount the vault
+// balance guard, mint A is writable so fees withheld in the vault can be harvested before it
+// is closed
+#[allow(clippy::too_many_arguments)]
+pub fn take_ix(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    vault_rent_recipient: &Pubkey,
+    fill_amount: Option<u64>,
+    max_receive: u64,
+    expected_vault_amount: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(*maker, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new(
+                get_associated_token_address(taker, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(taker, mint_b, token_program),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_b, token_program),
+                false,
+            ),
+            AccountMeta::new(*vault_rent_recipient, false),
+            AccountMeta::new(
+                get_associated_token_address(&TREASURY_ID, mint_b, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_address().0, false),
+        ],
+        data: crate::Instruction::Take(TakeInstructionData {
+            fill_amount,
+            max_receive,
+            expected_vault_amount,
+        })
+        .pack(),
+    }
+}
+
+// Same as take_ix, but mint A goes to the recipient's ATA instead of the taker's
+#[allow(clippy::too_many_arguments)]
+pub fn take_to_ix(
+    taker: &Pubkey,
+    recipient: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    vault_rent_recipient: &Pubkey,
+    fill_amount: Option<u64>,
+    max_receive: u64,
+    expected_vault_amount: u64,
+) -> Instruction {
+    let mut ix = take_ix(
+        taker,
+        maker,
+        mint_a,
+        mint_b,
+        token_program,
+        seed,
+        vault_rent_recipient,
+        fill_amount,
+        max_receive,
+        expected_vault_amount,
+    );
+
+    ix.accounts[6] = AccountMeta::new(
+        get_associated_token_address(recipient, mint_a, token_program),
+        false,
+    );
+    ix.accounts
+        .insert(0, AccountMeta::new_readonly(*recipient, false));
+    ix.data[0] = *TakeTo::DISCRIMINATOR;
+
+    ix
+}
+
+// Opens an escrow and fills it in the same instruction, both the maker and taker sign and the
+// vault rent goes back to the maker
+#[allow(clippy::too_many_arguments)]
+pub fn make_and_take_ix(
+    maker: &Pubkey,
+    taker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    receive: u64,
+    amount: u64,
+) -> Instruction {
+    let mut ix = make_ix(
+        maker,
+        mint_a,
+        mint_b,
+        token_program,
+        seed,
+        receive,
+        amount,
+        0,
+        0,
+        0,
+        0,
+        &Pubkey::default(),
+        &Pubkey::default(),
+        &[0; 32],
+    );
+    let take = take_ix(
+        taker,
+        maker,
+        mint_a,
+        mint_b,
+        token_program,
+        seed,
+        maker,
+        None,
+        0,
+        0,
+    );
+
+    ix.accounts.extend(take.accounts);
+    ix.data[0] = *MakeAndTake::DISCRIMINATOR;
+
+    ix
+}
+
+// Records the hash of the taker and a secret nonce, see CommitmentAccount::hash
+pub fn commit_take_ix(taker: &Pubkey, maker: &Pubkey, seed: u64, hash: [u8; 32]) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new_readonly(escrow, false),
+            AccountMeta::new(find_commitment_address(&escrow).0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: crate::Instruction::CommitTake(CommitTakeInstructionData { hash }).pack(),
+    }
+}
+
+// Same as take_ix, but only completes against the nonce committed by commit_take_ix
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_take_ix(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    vault_rent_recipient: &Pubkey,
+    nonce: [u8; 32],
+    fill_amount: Option<u64>,
+    max_receive: u64,
+    expected_vault_amount: u64,
+) -> Instruction {
+    let mut ix = take_ix(
+        taker,
+        maker,
+        mint_a,
+        mint_b,
+        token_program,
+        seed,
+        vault_rent_recipient,
+        fill_amount,
+        max_receive,
+        expected_vault_amount,
+    );
+
+    let escrow = find_escrow_address(maker, seed).0;
+    ix.accounts.insert(
+        0,
+        AccountMeta::new(find_commitment_address(&escrow).0, false),
+    );
+    ix.data = crate::Instruction::RevealTake(RevealTakeInstructionData {
+        nonce,
+        take: TakeInstructionData {
+            fill_amount,
+            max_receive,
+            expected_vault_amount,
+        },
+    })
+    .pack();
+
+    ix
+}
+
+// Same as take_ix for an escrow asking for the native mint, the maker and treasury are paid in
+// lamports so no ATAs for mint B are passed
+#[allow(clippy::too_many_arguments)]
+pub fn take_native_ix(
+    taker: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    vault_rent_recipient: &Pubkey,
+    fill_amount: Option<u64>,
+    max_receive: u64,
+    expected_vault_amount: u64,
+) -> Instruction {
+    let mut ix = take_ix(
+        taker,
+        maker,
+        mint_a,
+        &NATIVE_MINT_ID,
+        token_program,
+        seed,
+        vault_rent_recipient,
+        fill_amount,
+        max_receive,
+        expected_vault_amount,
+    );
+
+    ix.accounts[10] = AccountMeta::new(TREASURY_ID, false);
+    ix.accounts.drain(7..9);
+    ix.data[0] = *TakeNative::DISCRIMINATOR;
+
+    ix
+}
+
+pub fn refund_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(*mint_a, false),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        ],
+        data: crate::Instruction::Refund.pack(),
+    }
+}
+
+// Moves an escrow created before the discriminator was added to the current layout
+pub fn migrate_escrow_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: crate::Instruction::MigrateEscrow.pack(),
+    }
+}
+
+// Same as refund_ix with the tokens returned to the recipient's ATAs instead of the maker's
+pub fn refund_to_ix(
+    maker: &Pubkey,
+    recipient: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let mut ix = refund_ix(maker, mint_a, token_program, seed);
+
+    ix.accounts[4] = AccountMeta::new(
+        get_associated_token_address(recipient, mint_a, token_program),
+        false,
+    );
+    ix.accounts
+        .push(AccountMeta::new_readonly(*recipient, false));
+
+    ix
+}
+
+// Same as refund_ix with the reaper signing in place of the maker
+pub fn reap_ix(
+    reaper: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let mut ix = refund_ix(maker, mint_a, token_program, seed);
+
+    ix.accounts[0] = AccountMeta::new(*maker, false);
+    ix.accounts.insert(0, AccountMeta::new(*reaper, true));
+    ix.data[0] = *Reap::DISCRIMINATOR;
+
+    ix
+}
+
+// Only for escrows whose vault is already closed, the maker reclaims the escrow rent
+pub fn close_escrow_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: crate::Instruction::CloseEscrow.pack(),
+    }
+}
+
+pub fn split_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    new_seed: u64,
+    amount: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+    let new_escrow = find_escrow_address(maker, new_seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(new_escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new(get_vault_address(&new_escrow, mint_a, token_program), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_address().0, false),
+        ],
+        data: crate::Instruction::Split(SplitInstructionData {
+            seed: new_seed,
+            amount,
+        })
+        .pack(),
+    }
+}
+
+pub fn top_up_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    amount: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new_readonly(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: crate::Instruction::TopUp(TopUpInstructionData { amount }).pack(),
+    }
+}
+
+pub fn amend_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    amount: u64,
+    receive: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(find_config_address().0, false),
+        ],
+        data: crate::Instruction::Amend(AmendInstructionData { amount, receive }).pack(),
+    }
+}
+
+// The beneficiary is the escrow's authorized taker, or the maker for a self-vesting lock
+pub fn claim_ix(
+    beneficiary: &Pubkey,
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*beneficiary, true),
+            AccountMeta::new(*maker, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(*mint_a, false),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new(
+                get_associated_token_address(beneficiary, mint_a, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        ],
+        data: crate::Instruction::Claim.pack(),
+    }
+}
+
+// Replaces every receive option, panics with more than MAX_RECEIVE_OPTIONS
+pub fn set_receive_options_ix(maker: &Pubkey, seed: u64, options: &[(Pubkey, u64)]) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    let mut data = SetReceiveOptionsInstructionData {
+        options: ReceiveOptions::default(),
+        len: options.len(),
+    };
+    for (option, (mint, receive)) in data.options.iter_mut().zip(options) {
+        *option = ReceiveOption {
+            mint: mint.to_bytes(),
+            receive: *receive,
+        };
+    }
+    assert!(options.len() <= data.options.len());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*maker, true),
+            AccountMeta::new(escrow, false),
+        ],
+        data: crate::Instruction::SetReceiveOptions(data).pack(),
+    }
+}
+
+pub fn update_receive_ix(maker: &Pubkey, seed: u64, receive: u64) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*maker, true),
+            AccountMeta::new(escrow, false),
+        ],
+        data: crate::Instruction::UpdateReceive(UpdateReceiveInstructionData { receive }).pack(),
+    }
+}
+
+// The new expiry has to be later than the current one
+pub fn extend_expiry_ix(maker: &Pubkey, seed: u64, expiry: i64) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*maker, true),
+            AccountMeta::new(escrow, false),
+        ],
+        data: crate::Instruction::ExtendExpiry(ExtendExpiryInstructionData { expiry }).pack(),
+    }
+}
+
+// The escrow address stays derived from its creator, so keep using it after the transfer
+pub fn transfer_maker_ix(
+    maker: &Pubkey,
+    creator: &Pubkey,
+    seed: u64,
+    new_maker: &Pubkey,
+) -> Instruction {
+    let escrow = find_escrow_address(creator, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*maker, true),
+            AccountMeta::new(escrow, false),
+        ],
+        data: crate::Instruction::TransferMaker(TransferMakerInstructionData {
+            new_maker: new_maker.to_bytes(),
+        })
+        .pack(),
+    }
+}
+
+// Each escrow is given as the maker, seed and vault rent recipient it was made with
+pub fn take_many_ix(
+    taker: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    token_program: &Pubkey,
+    escrows: &[(Pubkey, u64, Pubkey)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*taker, true),
+        AccountMeta::new(*mint_a, false),
+        AccountMeta::new_readonly(*mint_b, false),
+        AccountMeta::new(
+            get_associated_token_address(taker, mint_a, token_program),
+            false,
+        ),
+        AccountMeta::new(
+            get_associated_token_address(taker, mint_b, token_program),
+            false,
+        ),
+        AccountMeta::new(
+            get_associated_token_address(&TREASURY_ID, mint_b, token_program),
+            false,
+        ),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+
+    for (maker, seed, vault_rent_recipient) in escrows {
+        let escrow = find_escrow_address(maker, *seed).0;
+
+        accounts.extend([
+            AccountMeta::new(*maker, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(get_vault_address(&escrow, mint_a, token_program), false),
+            AccountMeta::new(
+                get_associated_token_address(maker, mint_b, token_program),
+                false,
+            ),
+            AccountMeta::new(*vault_rent_recipient, false),
+        ]);
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: crate::Instruction::TakeMany(TakeManyInstructionData {
+            count: escrows.len() as u8,
+        })
+        .pack(),
+    }
+}
+
+// The admin must be the program's upgrade authority
+pub fn init_config_ix(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_program_data_address().0, false),
+        ],
+        data: crate::Instruction::InitConfig.pack(),
+    }
+}
+
+pub fn set_paused_ix(admin: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+        ],
+        data: crate::Instruction::SetPaused(SetPausedInstructionData { paused }).pack(),
+    }
+}
+
+pub fn set_fee_ix(admin: &Pubkey, fee_bps: u16) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+        ],
+        data: crate::Instruction::SetFee(SetFeeInstructionData { fee_bps }).pack(),
+    }
+}
+
+// Take builders pay the fee to TREASURY_ID, swap in the new recipient's ATA once this lands
+pub fn set_fee_recipient_ix(admin: &Pubkey, fee_recipient: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+        ],
+        data: crate::Instruction::SetFeeRecipient(SetFeeRecipientInstructionData {
+            fee_recipient: fee_recipient.to_bytes(),
+        })
+        .pack(),
+    }
+}
+
+pub fn set_min_deposit_ix(admin: &Pubkey, min_deposit: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+        ],
+        data: crate::Instruction::SetMinDeposit(SetMinDepositInstructionData { min_deposit })
+            .pack(),
+    }
+}
+
+pub fn query_escrow_ix(
+    maker: &Pubkey,
+    mint_a: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+) -> Instruction {
+    let escrow = find_escrow_address(maker, seed).0;
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*maker, false),
+            AccountMeta::new_readonly(escrow, false),
+            AccountMeta::new_readonly(get_vault_address(&escrow, mint_a, token_program), false),
+        ],
+        data: crate::Instruction::QueryEscrow.pack(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_pubkey::Pubkey;
+    use solana_rpc_client_api::filter::RpcFilterType;
+    use solana_signer::Signer;
+    use spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        solana_program::native_token::LAMPORTS_PER_SOL,
+    };
+    use spl_token_2022::state::Account as TokenAccount;
+
+    use super::{
+        find_escrow_address, find_escrow_address_with_prefix, get_associated_token_address,
+        get_vault_address, make_ix, maker_escrow_filters, refund_ix, take_ix,
+    };
+    use crate::tests::{
+        constants::{MINT_DECIMALS, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, TREASURY},
+        pda::{self, get_escrow_pda},
+        utils::{
+            build_and_send_transaction, fetch_account, init_ata, init_mint, init_wallet, setup,
+        },
+    };
+
+    #[test]
+    fn sdk_escrow_address_namespaces() {
+        let maker = Pubkey::new_unique();
+        let seed = 42u64;
+
+        let default_address = find_escrow_address(&maker, seed).0;
+        let namespaced_address = find_escrow_address_with_prefix(b"escrow-otc", &maker, seed).0;
+
+        assert_eq!(default_address, get_escrow_pda(&maker, seed));
+        assert_ne!(default_address, namespaced_address);
+    }
+
+    #[test]
+    fn sdk_vault_address() {
+        let escrow = find_escrow_address(&Pubkey::new_unique(), 42).0;
+        let mint_a = Pubkey::new_unique();
+
+        for token_program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+            let vault = get_vault_address(&escrow, &mint_a, &token_program);
+
+            assert_eq!(
+                vault,
+                get_associated_token_address_with_program_id(&escrow, &mint_a, &token_program)
+            );
+            assert_eq!(
+                vault,
+                pda::get_vault_address(&escrow, &mint_a, &token_program)
+            );
+        }
+    }
+
+    #[test]
+    fn sdk_make_and_take() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+        init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+        init_ata(litesvm, mint_b, TREASURY, 0);
+
+        let seed = 42u64;
+        let receive_amount: u64 = 100_000_000;
+        let give_amount: u64 = 500_000_000;
+
+        let ix = make_ix(
+            &maker.pubkey(),
+            &mint_a,
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+            seed,
+            receive_amount,
+            give_amount,
+            0,
+            0,
+            0,
+            0,
+            &Default::default(),
+            &Default::default(),
+            &[0u8; 32],
+        );
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let ix = take_ix(
+            &taker.pubkey(),
+            &maker.pubkey(),
+            &mint_a,
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+            seed,
+            &maker.pubkey(),
+            None,
+            receive_amount,
+            0,
+        );
+
+        let result = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let escrow_acc = litesvm.get_account(&get_escrow_pda(&maker.pubkey(), seed));
+
+        assert!(escrow_acc.is_none());
+
+        let maker_ata_b = get_associated_token_address(&maker.pubkey(), &mint_b, &TOKEN_PROGRAM_ID);
+
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_b).amount,
+            receive_amount
+        );
+    }
+
+    #[test]
+    fn sdk_make_and_refund() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+
+        let ix = make_ix(
+            &maker.pubkey(),
+            &mint_a,
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+            seed,
+            100_000_000,
+            500_000_000,
+            0,
+            0,
+            0,
+            0,
+            &Default::default(),
+            &Default::default(),
+            &[0u8; 32],
+        );
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let ix = refund_ix(&maker.pubkey(), &mint_a, &TOKEN_PROGRAM_ID, seed);
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            fetch_account::<TokenAccount>(litesvm, &maker_ata_a).amount,
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn sdk_maker_escrow_filters() {
+        let (litesvm, _default_payer) = &mut setup();
+
+        let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+        let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+        init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+
+        let seed = 42u64;
+
+        let ix = make_ix(
+            &maker.pubkey(),
+            &mint_a,
+            &mint_b,
+            &TOKEN_PROGRAM_ID,
+            seed,
+            100_000_000,
+            500_000_000,
+            0,
+            0,
+            0,
+            0,
+            &Default::default(),
+            &Default::default(),
+            &[0u8; 32],
+        );
+
+        let result = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix]);
+
+        assert!(result.is_ok());
+
+        let data = litesvm
+            .get_account(&get_escrow_pda(&maker.pubkey(), seed))
+            .unwrap()
+            .data;
+
+        // Every filter matches the maker's escrow, and the maker filter rejects anyone else's
+        let matches = |filters: Vec<RpcFilterType>| {
+            filters.iter().all(|filter| match filter {
+                RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+                RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+                _ => false,
+            })
+        };
+
+        assert!(matches(maker_escrow_filters(&maker.pubkey())));
+        assert!(!matches(maker_escrow_filters(&Pubkey::new_unique())));
+    }
+}
@@ -1,40 +1,428 @@
-use core::mem::{size_of, transmute};
+use core::mem::{align_of, offset_of, size_of, transmute};
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
+use crate::{MAX_BASKET_ENTRIES, MAX_RECEIVE_OPTIONS};
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct BasketEntry {
+    pub mint: Pubkey, // Extra token being deposited, zero for an unused entry
+    pub amount: u64,  // Amount of the extra token deposited in its vault
+}
+
+pub type Basket = [BasketEntry; MAX_BASKET_ENTRIES];
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct ReceiveOption {
+    pub mint: Pubkey, // Extra token accepted as payment, zero for an unused option
+    pub receive: u64, // Amount of the extra token wanted instead of the receive amount
+}
+
+pub type ReceiveOptions = [ReceiveOption; MAX_RECEIVE_OPTIONS];
+
 #[repr(C)]
 pub struct Escrow {
-    pub seed: u64,      // Random seed for PDA derivation
-    pub maker: Pubkey,  // Creator of the escrow
-    pub mint_a: Pubkey, // Token being deposited
-    pub mint_b: Pubkey, // Token being requested
-    pub receive: u64,   // Amount of token B wanted
-    pub bump: [u8; 1],  // PDA bump seed
+    pub discriminator: [u8; 8],       // Account type and layout version
+    pub seed: u64,                    // Random seed for PDA derivation
+    pub maker: Pubkey,                // Creator of the escrow
+    pub mint_a: Pubkey,               // Token being deposited
+    pub mint_b: Pubkey,               // Token being requested
+    pub receive: u64,                 // Amount of token B wanted
+    pub expiry: i64,                  // Unix timestamp the escrow expires at, 0 for never
+    pub start_time: i64,              // Unix timestamp the escrow can be taken from, 0 for now
+    pub vesting_start: i64,           // Unix timestamp the vault starts vesting from
+    pub vesting_duration: i64,        // Seconds until the vault is fully vested, 0 for no vesting
+    pub released: u64,                // Amount of token A claimed so far while vesting
+    pub authorized_taker: Pubkey,     // Only taker allowed to fill the escrow, zero for anyone
+    pub vault_rent_recipient: Pubkey, // Receives the vault rent when it is closed on Take
+    pub creator: Pubkey,              // Maker the PDA was derived from, kept across TransferMaker
+    pub basket: Basket,               // Extra tokens deposited alongside token A
+    pub options: ReceiveOptions,      // Extra tokens accepted as payment instead of token B
+    pub memo: [u8; 32],               // Off-chain reference set by the maker, stored as raw bytes
+    pub mint_a_decimals: u8,          // Decimals of token A, so transfers don't re-read the mint
+    pub mint_b_decimals: u8,          // Decimals of token B, for clients displaying the receive
+    pub receive_native: bool,         // Receive is paid in lamports through TakeNative
+    pub bump: [u8; 1],                // PDA bump seed
+    pub fee_bps: u16,                 // Protocol fee rate in force at Make, in basis points
 }
 
 impl Escrow {
-    pub const LEN: usize = size_of::<u64>()
+    // Bump it whenever the layout changes so older accounts are never misread
+    pub const DISCRIMINATOR: [u8; 8] = *b"escrowv2";
+
+    // Escrows created before the fee rate was stored end at the bump, until MigrateEscrow
+    // resizes them
+    pub const V1_DISCRIMINATOR: [u8; 8] = *b"escrowv1";
+    pub const V1_LEN: usize = Escrow::LEN - size_of::<u16>();
+
+    pub const LEN: usize = size_of::<[u8; 8]>()
+        + size_of::<u64>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<u64>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<Basket>()
+        + size_of::<ReceiveOptions>()
+        + size_of::<[u8; 32]>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<bool>()
+        + size_of::<[u8; 1]>()
+        + size_of::<u16>();
+
+    // Escrows created before the discriminator was added hold only the seed, maker, mint A,
+    // mint B, receive and bump, packed with no padding, until MigrateEscrow resizes them
+    pub const LEGACY_LEN: usize = size_of::<u64>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<u64>()
         + size_of::<[u8; 1]>();
 
+    // Only for a freshly created account, which has no discriminator until set_inner
     #[inline(always)]
-    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+    pub fn load_mut_uninitialized(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
         if bytes.len() != Escrow::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        bytes[offset_of!(Self, receive_native)] = 0;
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    // A receive_native byte other than 0 or 1 isn't a valid bool, so it is rejected before the cast
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Escrow::LEN
+            || bytes[..8] != Self::DISCRIMINATOR
+            || bytes[offset_of!(Self, receive_native)] > 1
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
         Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
     }
 
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != Escrow::LEN {
+        if bytes.len() != Escrow::LEN
+            || bytes[..8] != Self::DISCRIMINATOR
+            || bytes[offset_of!(Self, receive_native)] > 1
+        {
             return Err(ProgramError::InvalidAccountData);
         }
         Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
     }
 
+    // Checked cast for clients, rejects buffers that aren't aligned for Escrow
+    #[inline(always)]
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Escrow::LEN
+            || !(bytes.as_ptr() as usize).is_multiple_of(align_of::<Self>())
+            || bytes[..8] != Self::DISCRIMINATOR
+            || bytes[offset_of!(Self, receive_native)] > 1
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    // Owned copy for readers that can't guarantee alignment, parsed field by field
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let bytes: &[u8; Escrow::LEN] = bytes
+            .try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if bytes[..8] != Self::DISCRIMINATOR || bytes[offset_of!(Self, receive_native)] > 1 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            discriminator: Self::DISCRIMINATOR,
+            seed: u64::from_le_bytes(field(bytes, offset_of!(Self, seed))),
+            maker: field(bytes, offset_of!(Self, maker)),
+            mint_a: field(bytes, offset_of!(Self, mint_a)),
+            mint_b: field(bytes, offset_of!(Self, mint_b)),
+            receive: u64::from_le_bytes(field(bytes, offset_of!(Self, receive))),
+            expiry: i64::from_le_bytes(field(bytes, offset_of!(Self, expiry))),
+            start_time: i64::from_le_bytes(field(bytes, offset_of!(Self, start_time))),
+            vesting_start: i64::from_le_bytes(field(bytes, offset_of!(Self, vesting_start))),
+            vesting_duration: i64::from_le_bytes(field(bytes, offset_of!(Self, vesting_duration))),
+            released: u64::from_le_bytes(field(bytes, offset_of!(Self, released))),
+            authorized_taker: field(bytes, offset_of!(Self, authorized_taker)),
+            vault_rent_recipient: field(bytes, offset_of!(Self, vault_rent_recipient)),
+            creator: field(bytes, offset_of!(Self, creator)),
+            basket: core::array::from_fn(|index| {
+                let offset = offset_of!(Self, basket) + index * size_of::<BasketEntry>();
+                BasketEntry {
+                    mint: field(bytes, offset + offset_of!(BasketEntry, mint)),
+                    amount: u64::from_le_bytes(field(
+                        bytes,
+                        offset + offset_of!(BasketEntry, amount),
+                    )),
+                }
+            }),
+            options: core::array::from_fn(|index| {
+                let offset = offset_of!(Self, options) + index * size_of::<ReceiveOption>();
+                ReceiveOption {
+                    mint: field(bytes, offset + offset_of!(ReceiveOption, mint)),
+                    receive: u64::from_le_bytes(field(
+                        bytes,
+                        offset + offset_of!(ReceiveOption, receive),
+                    )),
+                }
+            }),
+            memo: field(bytes, offset_of!(Self, memo)),
+            mint_a_decimals: bytes[offset_of!(Self, mint_a_decimals)],
+            mint_b_decimals: bytes[offset_of!(Self, mint_b_decimals)],
+            receive_native: bytes[offset_of!(Self, receive_native)] == 1,
+            bump: field(bytes, offset_of!(Self, bump)),
+            fee_bps: u16::from_le_bytes(field(bytes, offset_of!(Self, fee_bps))),
+        })
+    }
+
+    // Serialized form matches the account data byte for byte
+    pub fn to_bytes(&self) -> [u8; Escrow::LEN] {
+        let mut bytes = [0u8; Escrow::LEN];
+
+        put(
+            &mut bytes,
+            offset_of!(Self, discriminator),
+            &self.discriminator,
+        );
+        put(&mut bytes, offset_of!(Self, seed), &self.seed.to_le_bytes());
+        put(&mut bytes, offset_of!(Self, maker), &self.maker);
+        put(&mut bytes, offset_of!(Self, mint_a), &self.mint_a);
+        put(&mut bytes, offset_of!(Self, mint_b), &self.mint_b);
+        put(
+            &mut bytes,
+            offset_of!(Self, receive),
+            &self.receive.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, expiry),
+            &self.expiry.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, start_time),
+            &self.start_time.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, vesting_start),
+            &self.vesting_start.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, vesting_duration),
+            &self.vesting_duration.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, released),
+            &self.released.to_le_bytes(),
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, authorized_taker),
+            &self.authorized_taker,
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, vault_rent_recipient),
+            &self.vault_rent_recipient,
+        );
+        put(&mut bytes, offset_of!(Self, creator), &self.creator);
+        for (index, entry) in self.basket.iter().enumerate() {
+            let offset = offset_of!(Self, basket) + index * size_of::<BasketEntry>();
+            put(
+                &mut bytes,
+                offset + offset_of!(BasketEntry, mint),
+                &entry.mint,
+            );
+            put(
+                &mut bytes,
+                offset + offset_of!(BasketEntry, amount),
+                &entry.amount.to_le_bytes(),
+            );
+        }
+        for (index, option) in self.options.iter().enumerate() {
+            let offset = offset_of!(Self, options) + index * size_of::<ReceiveOption>();
+            put(
+                &mut bytes,
+                offset + offset_of!(ReceiveOption, mint),
+                &option.mint,
+            );
+            put(
+                &mut bytes,
+                offset + offset_of!(ReceiveOption, receive),
+                &option.receive.to_le_bytes(),
+            );
+        }
+        put(&mut bytes, offset_of!(Self, memo), &self.memo);
+        put(
+            &mut bytes,
+            offset_of!(Self, mint_a_decimals),
+            &[self.mint_a_decimals],
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, mint_b_decimals),
+            &[self.mint_b_decimals],
+        );
+        put(
+            &mut bytes,
+            offset_of!(Self, receive_native),
+            &[self.receive_native as u8],
+        );
+        put(&mut bytes, offset_of!(Self, bump), &self.bump);
+        put(
+            &mut bytes,
+            offset_of!(Self, fee_bps),
+            &self.fee_bps.to_le_bytes(),
+        );
+
+        bytes
+    }
+
+    #[inline(always)]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline(always)]
+    pub fn maker(&self) -> &Pubkey {
+        &self.maker
+    }
+
+    #[inline(always)]
+    pub fn mint_a(&self) -> &Pubkey {
+        &self.mint_a
+    }
+
+    #[inline(always)]
+    pub fn mint_b(&self) -> &Pubkey {
+        &self.mint_b
+    }
+
+    #[inline(always)]
+    pub fn receive(&self) -> u64 {
+        self.receive
+    }
+
+    #[inline(always)]
+    pub fn expiry(&self) -> i64 {
+        self.expiry
+    }
+
+    #[inline(always)]
+    pub fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    #[inline(always)]
+    pub fn vesting_start(&self) -> i64 {
+        self.vesting_start
+    }
+
+    #[inline(always)]
+    pub fn vesting_duration(&self) -> i64 {
+        self.vesting_duration
+    }
+
+    #[inline(always)]
+    pub fn released(&self) -> u64 {
+        self.released
+    }
+
+    #[inline(always)]
+    pub fn authorized_taker(&self) -> &Pubkey {
+        &self.authorized_taker
+    }
+
+    #[inline(always)]
+    pub fn vault_rent_recipient(&self) -> &Pubkey {
+        &self.vault_rent_recipient
+    }
+
+    #[inline(always)]
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+
+    #[inline(always)]
+    pub fn memo(&self) -> &[u8; 32] {
+        &self.memo
+    }
+
+    // Entries in use, which are always packed at the front
+    #[inline(always)]
+    pub fn basket(&self) -> &[BasketEntry] {
+        let len = self
+            .basket
+            .iter()
+            .take_while(|entry| entry.mint.ne(&[0u8; 32]))
+            .count();
+        &self.basket[..len]
+    }
+
+    // Options in use, which are always packed at the front
+    #[inline(always)]
+    pub fn receive_options(&self) -> &[ReceiveOption] {
+        let len = self
+            .options
+            .iter()
+            .take_while(|option| option.mint.ne(&[0u8; 32]))
+            .count();
+        &self.options[..len]
+    }
+
+    // Amount wanted when paying in the given mint, None if the maker doesn't accept it
+    #[inline(always)]
+    pub fn receive_for(&self, mint: &Pubkey) -> Option<u64> {
+        if self.mint_b.eq(mint) {
+            return Some(self.receive);
+        }
+
+        self.receive_options()
+            .iter()
+            .find(|option| option.mint.eq(mint))
+            .map(|option| option.receive)
+    }
+
+    #[inline(always)]
+    pub fn mint_a_decimals(&self) -> u8 {
+        self.mint_a_decimals
+    }
+
+    #[inline(always)]
+    pub fn mint_b_decimals(&self) -> u8 {
+        self.mint_b_decimals
+    }
+
+    #[inline(always)]
+    pub fn receive_native(&self) -> bool {
+        self.receive_native
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    #[inline(always)]
+    pub fn fee_bps(&self) -> u16 {
+        self.fee_bps
+    }
+
     #[inline(always)]
     pub fn set_seed(&mut self, seed: u64) {
         self.seed = seed;
@@ -60,12 +448,88 @@ impl Escrow {
         self.receive = receive;
     }
 
+    #[inline(always)]
+    pub fn set_expiry(&mut self, expiry: i64) {
+        self.expiry = expiry;
+    }
+
+    #[inline(always)]
+    pub fn set_start_time(&mut self, start_time: i64) {
+        self.start_time = start_time;
+    }
+
+    #[inline(always)]
+    pub fn set_vesting_start(&mut self, vesting_start: i64) {
+        self.vesting_start = vesting_start;
+    }
+
+    #[inline(always)]
+    pub fn set_vesting_duration(&mut self, vesting_duration: i64) {
+        self.vesting_duration = vesting_duration;
+    }
+
+    #[inline(always)]
+    pub fn set_released(&mut self, released: u64) {
+        self.released = released;
+    }
+
+    #[inline(always)]
+    pub fn set_authorized_taker(&mut self, authorized_taker: Pubkey) {
+        self.authorized_taker = authorized_taker;
+    }
+
+    #[inline(always)]
+    pub fn set_vault_rent_recipient(&mut self, vault_rent_recipient: Pubkey) {
+        self.vault_rent_recipient = vault_rent_recipient;
+    }
+
+    #[inline(always)]
+    pub fn set_memo(&mut self, memo: [u8; 32]) {
+        self.memo = memo;
+    }
+
+    #[inline(always)]
+    pub fn set_basket_entry(&mut self, index: usize, mint: Pubkey, amount: u64) {
+        self.basket[index] = BasketEntry { mint, amount };
+    }
+
+    #[inline(always)]
+    pub fn set_creator(&mut self, creator: Pubkey) {
+        self.creator = creator;
+    }
+
+    #[inline(always)]
+    pub fn set_receive_option(&mut self, index: usize, mint: Pubkey, receive: u64) {
+        self.options[index] = ReceiveOption { mint, receive };
+    }
+
+    #[inline(always)]
+    pub fn set_mint_a_decimals(&mut self, mint_a_decimals: u8) {
+        self.mint_a_decimals = mint_a_decimals;
+    }
+
+    #[inline(always)]
+    pub fn set_mint_b_decimals(&mut self, mint_b_decimals: u8) {
+        self.mint_b_decimals = mint_b_decimals;
+    }
+
+    #[inline(always)]
+    pub fn set_receive_native(&mut self, receive_native: bool) {
+        self.receive_native = receive_native;
+    }
+
     #[inline(always)]
     pub fn set_bump(&mut self, bump: [u8; 1]) {
         self.bump = bump;
     }
 
     #[inline(always)]
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps;
+    }
+
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
     pub fn set_inner(
         &mut self,
         seed: u64,
@@ -73,13 +537,735 @@ impl Escrow {
         mint_a: Pubkey,
         mint_b: Pubkey,
         receive: u64,
+        expiry: i64,
+        start_time: i64,
+        vesting_start: i64,
+        vesting_duration: i64,
+        authorized_taker: Pubkey,
+        vault_rent_recipient: Pubkey,
+        memo: [u8; 32],
+        mint_a_decimals: u8,
+        mint_b_decimals: u8,
+        receive_native: bool,
         bump: [u8; 1],
+        fee_bps: u16,
     ) {
+        self.discriminator = Self::DISCRIMINATOR;
         self.seed = seed;
         self.maker = maker;
         self.mint_a = mint_a;
         self.mint_b = mint_b;
         self.receive = receive;
+        self.expiry = expiry;
+        self.start_time = start_time;
+        self.vesting_start = vesting_start;
+        self.vesting_duration = vesting_duration;
+        self.released = 0;
+        self.authorized_taker = authorized_taker;
+        self.vault_rent_recipient = vault_rent_recipient;
+        self.creator = maker;
+        self.basket = Basket::default();
+        self.options = ReceiveOptions::default();
+        self.memo = memo;
+        self.mint_a_decimals = mint_a_decimals;
+        self.mint_b_decimals = mint_b_decimals;
+        self.receive_native = receive_native;
+        self.bump = bump;
+        self.fee_bps = fee_bps;
+    }
+}
+
+// Fields are packed with no interior padding, so the account data is exactly LEN bytes
+const _: () = assert!(offset_of!(Escrow, fee_bps) == Escrow::LEN - size_of::<u16>());
+const _: () = assert!(size_of::<Escrow>() == Escrow::LEN.next_multiple_of(align_of::<Escrow>()));
+
+#[inline(always)]
+fn field<const N: usize>(bytes: &[u8; Escrow::LEN], offset: usize) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&bytes[offset..offset + N]);
+    out
+}
+
+#[inline(always)]
+fn put(bytes: &mut [u8; Escrow::LEN], offset: usize, value: &[u8]) {
+    bytes[offset..offset + value.len()].copy_from_slice(value);
+}
+
+#[repr(C)]
+pub struct Config {
+    pub discriminator: [u8; 8], // Account type and layout version
+    pub admin: Pubkey,          // Only signer allowed to change the config
+    pub fee_recipient: Pubkey,  // Wallet the protocol fee is paid to on every Take
+    pub fee_bps: [u8; 2],       // Protocol fee taken from the receive amount, in basis points
+    pub min_deposit: [u8; 8],   // Smallest amount of token A Make accepts
+    pub paused: bool,           // Make and Take are rejected while set
+    pub bump: [u8; 1],          // PDA bump seed
+}
+
+impl Config {
+    pub const DISCRIMINATOR: [u8; 8] = *b"configv3";
+
+    pub const LEN: usize = size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 2]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<bool>()
+        + size_of::<[u8; 1]>();
+
+    // Only for a freshly created account, which has no discriminator until set_inner
+    #[inline(always)]
+    pub fn load_mut_uninitialized(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Config::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytes[offset_of!(Self, paused)] = 0;
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    // A paused byte other than 0 or 1 isn't a valid bool, so it is rejected before the cast
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Config::LEN
+            || bytes[..8] != Self::DISCRIMINATOR
+            || bytes[offset_of!(Self, paused)] > 1
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Config::LEN
+            || bytes[..8] != Self::DISCRIMINATOR
+            || bytes[offset_of!(Self, paused)] > 1
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn admin(&self) -> &Pubkey {
+        &self.admin
+    }
+
+    #[inline(always)]
+    pub fn fee_recipient(&self) -> &Pubkey {
+        &self.fee_recipient
+    }
+
+    #[inline(always)]
+    pub fn fee_bps(&self) -> u16 {
+        u16::from_le_bytes(self.fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn min_deposit(&self) -> u64 {
+        u64::from_le_bytes(self.min_deposit)
+    }
+
+    #[inline(always)]
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    #[inline(always)]
+    pub fn set_admin(&mut self, admin: Pubkey) {
+        self.admin = admin;
+    }
+
+    #[inline(always)]
+    pub fn set_fee_recipient(&mut self, fee_recipient: Pubkey) {
+        self.fee_recipient = fee_recipient;
+    }
+
+    #[inline(always)]
+    pub fn set_fee_bps(&mut self, fee_bps: u16) {
+        self.fee_bps = fee_bps.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_min_deposit(&mut self, min_deposit: u64) {
+        self.min_deposit = min_deposit.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        admin: Pubkey,
+        fee_recipient: Pubkey,
+        fee_bps: u16,
+        min_deposit: u64,
+        bump: [u8; 1],
+    ) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.admin = admin;
+        self.fee_recipient = fee_recipient;
+        self.fee_bps = fee_bps.to_le_bytes();
+        self.min_deposit = min_deposit.to_le_bytes();
+        self.paused = false;
         self.bump = bump;
     }
 }
+
+// Every field is byte aligned, so Config can be cast from account data at any address
+const _: () = assert!(align_of::<Config>() == 1);
+const _: () = assert!(size_of::<Config>() == Config::LEN);
+
+#[repr(C)]
+pub struct Commitment {
+    pub discriminator: [u8; 8], // Account type and layout version
+    pub escrow: Pubkey,         // Escrow the pending Take is for
+    pub hash: [u8; 32],         // sha256 of the taker and a secret nonce
+    pub slot: [u8; 8],          // Slot the commitment was made in
+    pub bump: [u8; 1],          // PDA bump seed
+}
+
+impl Commitment {
+    pub const DISCRIMINATOR: [u8; 8] = *b"commitv1";
+
+    pub const LEN: usize = size_of::<[u8; 8]>()
+        + size_of::<Pubkey>()
+        + size_of::<[u8; 32]>()
+        + size_of::<[u8; 8]>()
+        + size_of::<[u8; 1]>();
+
+    // Only for a freshly created account, which has no discriminator until set_inner
+    #[inline(always)]
+    pub fn load_mut_uninitialized(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Commitment::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Commitment::LEN || bytes[..8] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != Commitment::LEN || bytes[..8] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn escrow(&self) -> &Pubkey {
+        &self.escrow
+    }
+
+    #[inline(always)]
+    pub fn hash(&self) -> &[u8; 32] {
+        &self.hash
+    }
+
+    #[inline(always)]
+    pub fn slot(&self) -> u64 {
+        u64::from_le_bytes(self.slot)
+    }
+
+    #[inline(always)]
+    pub fn bump(&self) -> u8 {
+        self.bump[0]
+    }
+
+    #[inline(always)]
+    pub fn set_commitment(&mut self, hash: [u8; 32], slot: u64) {
+        self.hash = hash;
+        self.slot = slot.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, escrow: Pubkey, hash: [u8; 32], slot: u64, bump: [u8; 1]) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.escrow = escrow;
+        self.set_commitment(hash, slot);
+        self.bump = bump;
+    }
+}
+
+// Every field is byte aligned, so Commitment can be cast from account data at any address
+const _: () = assert!(align_of::<Commitment>() == 1);
+const _: () = assert!(size_of::<Commitment>() == Commitment::LEN);
+
+#[cfg(test)]
+mod tests {
+    use pinocchio::program_error::ProgramError;
+
+    use crate::{ESCROW_MAKER_OFFSET, Escrow};
+
+    #[repr(C, align(8))]
+    struct AlignedBuffer([u8; Escrow::LEN + 1]);
+
+    #[test]
+    fn escrow_accessors() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                200,
+                150,
+                300,
+                400,
+                [4; 32],
+                [5; 32],
+                [6; 32],
+                9,
+                6,
+                true,
+                [255],
+                50,
+            );
+
+        let escrow = Escrow::try_from_bytes(&buffer.0[..Escrow::LEN]).unwrap();
+
+        assert_eq!(escrow.seed(), 42);
+        assert_eq!(escrow.maker(), &[1; 32]);
+        assert_eq!(escrow.mint_a(), &[2; 32]);
+        assert_eq!(escrow.mint_b(), &[3; 32]);
+        assert_eq!(escrow.receive(), 100);
+        assert_eq!(escrow.expiry(), 200);
+        assert_eq!(escrow.start_time(), 150);
+        assert_eq!(escrow.vesting_start(), 300);
+        assert_eq!(escrow.vesting_duration(), 400);
+        assert_eq!(escrow.released(), 0);
+        assert_eq!(escrow.authorized_taker(), &[4; 32]);
+        assert_eq!(escrow.vault_rent_recipient(), &[5; 32]);
+        assert_eq!(escrow.creator(), &[1; 32]);
+        assert_eq!(escrow.memo(), &[6; 32]);
+        assert_eq!(escrow.mint_a_decimals(), 9);
+        assert_eq!(escrow.mint_b_decimals(), 6);
+        assert!(escrow.receive_native());
+        assert_eq!(escrow.bump(), 255);
+        assert_eq!(escrow.fee_bps(), 50);
+    }
+
+    #[test]
+    fn escrow_receive_for() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        let escrow = Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN]).unwrap();
+        escrow.set_inner(
+            42,
+            [1; 32],
+            [2; 32],
+            [3; 32],
+            100,
+            0,
+            0,
+            0,
+            0,
+            [0; 32],
+            [1; 32],
+            [0; 32],
+            6,
+            6,
+            false,
+            [255],
+            50,
+        );
+
+        assert!(escrow.receive_options().is_empty());
+
+        escrow.set_receive_option(0, [7; 32], 500);
+
+        assert_eq!(escrow.receive_options().len(), 1);
+        assert_eq!(escrow.receive_for(&[3; 32]), Some(100));
+        assert_eq!(escrow.receive_for(&[7; 32]), Some(500));
+        assert_eq!(escrow.receive_for(&[0; 32]), None);
+        assert_eq!(escrow.receive_for(&[8; 32]), None);
+    }
+
+    #[test]
+    fn escrow_try_from_bytes_rejects_invalid_buffers() {
+        let buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        assert_eq!(
+            Escrow::try_from_bytes(&buffer.0).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::try_from_bytes(&buffer.0[1..]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn escrow_rejects_unknown_discriminator() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                200,
+                150,
+                300,
+                400,
+                [4; 32],
+                [5; 32],
+                [6; 32],
+                9,
+                6,
+                true,
+                [255],
+                50,
+            );
+
+        assert!(Escrow::load(&buffer.0[..Escrow::LEN]).is_ok());
+
+        // An older layout version is not readable as the current one
+        buffer.0[..8].copy_from_slice(b"escrowv1");
+
+        assert_eq!(
+            Escrow::load(&buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::load_mut(&mut buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::try_from_bytes(&buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn escrow_to_bytes_matches_account_data() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                200,
+                150,
+                300,
+                400,
+                [4; 32],
+                [5; 32],
+                [6; 32],
+                9,
+                6,
+                true,
+                [255],
+                50,
+            );
+        Escrow::load_mut(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_receive_option(0, [7; 32], 500);
+
+        let bytes = Escrow::load(&buffer.0[..Escrow::LEN]).unwrap().to_bytes();
+
+        assert_eq!(bytes, buffer.0[..Escrow::LEN]);
+        assert_eq!(&bytes[..8], b"escrowv2");
+        assert_eq!(bytes[8..16], 42u64.to_le_bytes());
+        assert_eq!(bytes[16..48], [1; 32]);
+        assert_eq!(bytes[48..80], [2; 32]);
+        assert_eq!(bytes[80..112], [3; 32]);
+        assert_eq!(bytes[112..120], 100u64.to_le_bytes());
+        assert_eq!(bytes[120..128], 200i64.to_le_bytes());
+        assert_eq!(bytes[128..136], 150i64.to_le_bytes());
+        assert_eq!(bytes[136..144], 300i64.to_le_bytes());
+        assert_eq!(bytes[144..152], 400i64.to_le_bytes());
+        assert_eq!(bytes[152..160], 0u64.to_le_bytes());
+        assert_eq!(bytes[160..192], [4; 32]);
+        assert_eq!(bytes[192..224], [5; 32]);
+        assert_eq!(bytes[224..256], [1; 32]);
+        assert_eq!(bytes[256..336], [0; 80]);
+        assert_eq!(bytes[336..368], [7; 32]);
+        assert_eq!(bytes[368..376], 500u64.to_le_bytes());
+        assert_eq!(bytes[376..416], [0; 40]);
+        assert_eq!(bytes[416..448], [6; 32]);
+        assert_eq!(bytes[448], 9);
+        assert_eq!(bytes[449], 6);
+        assert_eq!(bytes[450], 1);
+        assert_eq!(bytes[451], 255);
+    }
+
+    #[test]
+    fn escrow_maker_offset() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                0,
+                0,
+                0,
+                0,
+                [0; 32],
+                [0; 32],
+                [0; 32],
+                6,
+                6,
+                false,
+                [255],
+                50,
+            );
+
+        let bytes = Escrow::load(&buffer.0[..Escrow::LEN]).unwrap().to_bytes();
+
+        // The maker follows the discriminator and seed in the account data
+        assert_eq!(ESCROW_MAKER_OFFSET, 16);
+        assert_eq!(
+            bytes[ESCROW_MAKER_OFFSET..ESCROW_MAKER_OFFSET + 32],
+            [1; 32]
+        );
+    }
+
+    #[test]
+    fn escrow_from_bytes_round_trips() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                200,
+                150,
+                300,
+                400,
+                [4; 32],
+                [5; 32],
+                [6; 32],
+                9,
+                6,
+                true,
+                [255],
+                50,
+            );
+
+        // Misaligned on purpose, which try_from_bytes would reject
+        buffer.0.copy_within(..Escrow::LEN, 1);
+        let escrow = Escrow::from_bytes(&buffer.0[1..]).unwrap();
+
+        assert_eq!(escrow.seed(), 42);
+        assert_eq!(escrow.maker(), &[1; 32]);
+        assert_eq!(escrow.receive(), 100);
+        assert_eq!(escrow.vesting_duration(), 400);
+        assert_eq!(escrow.vault_rent_recipient(), &[5; 32]);
+        assert_eq!(escrow.bump(), 255);
+        assert_eq!(escrow.to_bytes(), buffer.0[1..]);
+
+        assert_eq!(
+            Escrow::from_bytes(&buffer.0).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::from_bytes(&[0; Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn escrow_rejects_invalid_receive_native() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN])
+            .unwrap()
+            .set_inner(
+                42,
+                [1; 32],
+                [2; 32],
+                [3; 32],
+                100,
+                200,
+                150,
+                300,
+                400,
+                [4; 32],
+                [5; 32],
+                [6; 32],
+                9,
+                6,
+                false,
+                [255],
+                50,
+            );
+
+        // Only 0 and 1 are valid bools
+        buffer.0[450] = 2;
+
+        assert_eq!(
+            Escrow::load(&buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::load_mut(&mut buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Escrow::from_bytes(&buffer.0[..Escrow::LEN]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    // Byte offsets of every Escrow field in deployed account data. These are written out rather
+    // than taken from offset_of! so reordering or resizing a field fails the test below, any
+    // change to them needs a new Escrow::DISCRIMINATOR and a migration
+    const DISCRIMINATOR_OFFSET: usize = 0;
+    const SEED_OFFSET: usize = 8;
+    const MAKER_OFFSET: usize = 16;
+    const MINT_A_OFFSET: usize = 48;
+    const MINT_B_OFFSET: usize = 80;
+    const RECEIVE_OFFSET: usize = 112;
+    const EXPIRY_OFFSET: usize = 120;
+    const START_TIME_OFFSET: usize = 128;
+    const VESTING_START_OFFSET: usize = 136;
+    const VESTING_DURATION_OFFSET: usize = 144;
+    const RELEASED_OFFSET: usize = 152;
+    const AUTHORIZED_TAKER_OFFSET: usize = 160;
+    const VAULT_RENT_RECIPIENT_OFFSET: usize = 192;
+    const CREATOR_OFFSET: usize = 224;
+    const BASKET_OFFSET: usize = 256;
+    const OPTIONS_OFFSET: usize = 336;
+    const MEMO_OFFSET: usize = 416;
+    const MINT_A_DECIMALS_OFFSET: usize = 448;
+    const MINT_B_DECIMALS_OFFSET: usize = 449;
+    const RECEIVE_NATIVE_OFFSET: usize = 450;
+    const BUMP_OFFSET: usize = 451;
+    const FEE_BPS_OFFSET: usize = 452;
+    const ESCROW_LEN: usize = 454;
+
+    #[test]
+    fn escrow_layout_is_stable() {
+        let mut buffer = AlignedBuffer([0; Escrow::LEN + 1]);
+
+        let escrow = Escrow::load_mut_uninitialized(&mut buffer.0[..Escrow::LEN]).unwrap();
+        escrow.set_inner(
+            42,
+            [1; 32],
+            [2; 32],
+            [3; 32],
+            100,
+            200,
+            150,
+            300,
+            400,
+            [4; 32],
+            [5; 32],
+            [6; 32],
+            9,
+            6,
+            true,
+            [255],
+            50,
+        );
+        escrow.set_released(50);
+        escrow.set_creator([7; 32]);
+        escrow.set_basket_entry(0, [8; 32], 250);
+        escrow.set_basket_entry(1, [9; 32], 350);
+        escrow.set_receive_option(0, [10; 32], 450);
+        escrow.set_receive_option(1, [11; 32], 550);
+
+        let bytes = Escrow::load(&buffer.0[..Escrow::LEN]).unwrap().to_bytes();
+
+        assert_eq!(Escrow::LEN, ESCROW_LEN);
+        assert_eq!(bytes[..], buffer.0[..Escrow::LEN]);
+
+        assert_eq!(bytes[DISCRIMINATOR_OFFSET..SEED_OFFSET], *b"escrowv2");
+        assert_eq!(bytes[SEED_OFFSET..MAKER_OFFSET], 42u64.to_le_bytes());
+        assert_eq!(bytes[MAKER_OFFSET..MINT_A_OFFSET], [1; 32]);
+        assert_eq!(bytes[MINT_A_OFFSET..MINT_B_OFFSET], [2; 32]);
+        assert_eq!(bytes[MINT_B_OFFSET..RECEIVE_OFFSET], [3; 32]);
+        assert_eq!(bytes[RECEIVE_OFFSET..EXPIRY_OFFSET], 100u64.to_le_bytes());
+        assert_eq!(
+            bytes[EXPIRY_OFFSET..START_TIME_OFFSET],
+            200i64.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[START_TIME_OFFSET..VESTING_START_OFFSET],
+            150i64.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[VESTING_START_OFFSET..VESTING_DURATION_OFFSET],
+            300i64.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[VESTING_DURATION_OFFSET..RELEASED_OFFSET],
+            400i64.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[RELEASED_OFFSET..AUTHORIZED_TAKER_OFFSET],
+            50u64.to_le_bytes()
+        );
+        assert_eq!(
+            bytes[AUTHORIZED_TAKER_OFFSET..VAULT_RENT_RECIPIENT_OFFSET],
+            [4; 32]
+        );
+        assert_eq!(bytes[VAULT_RENT_RECIPIENT_OFFSET..CREATOR_OFFSET], [5; 32]);
+        assert_eq!(bytes[CREATOR_OFFSET..BASKET_OFFSET], [7; 32]);
+
+        // Basket entries and receive options are each a mint followed by an amount
+        assert_eq!(bytes[BASKET_OFFSET..BASKET_OFFSET + 32], [8; 32]);
+        assert_eq!(
+            bytes[BASKET_OFFSET + 32..BASKET_OFFSET + 40],
+            250u64.to_le_bytes()
+        );
+        assert_eq!(bytes[BASKET_OFFSET + 40..BASKET_OFFSET + 72], [9; 32]);
+        assert_eq!(
+            bytes[BASKET_OFFSET + 72..OPTIONS_OFFSET],
+            350u64.to_le_bytes()
+        );
+        assert_eq!(bytes[OPTIONS_OFFSET..OPTIONS_OFFSET + 32], [10; 32]);
+        assert_eq!(
+            bytes[OPTIONS_OFFSET + 32..OPTIONS_OFFSET + 40],
+            450u64.to_le_bytes()
+        );
+        assert_eq!(bytes[OPTIONS_OFFSET + 40..OPTIONS_OFFSET + 72], [11; 32]);
+        assert_eq!(
+            bytes[OPTIONS_OFFSET + 72..MEMO_OFFSET],
+            550u64.to_le_bytes()
+        );
+
+        assert_eq!(bytes[MEMO_OFFSET..MINT_A_DECIMALS_OFFSET], [6; 32]);
+        assert_eq!(bytes[MINT_A_DECIMALS_OFFSET], 9);
+        assert_eq!(bytes[MINT_B_DECIMALS_OFFSET], 6);
+        assert_eq!(bytes[RECEIVE_NATIVE_OFFSET], 1);
+        assert_eq!(bytes[BUMP_OFFSET], 255);
+        assert_eq!(bytes[FEE_BPS_OFFSET..ESCROW_LEN], 50u16.to_le_bytes());
+    }
+}
@@ -0,0 +1,112 @@
+use solana_instruction::{AccountMeta, Instruction};
+use solana_signer::Signer;
+use spl_associated_token_account::solana_program::native_token::LAMPORTS_PER_SOL;
+
+use crate::tests::{
+    constants::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, MINT_DECIMALS, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+        TOKEN_PROGRAM_ID, TREASURY,
+    },
+    pda::{get_config_pda, get_escrow_pda, get_vault_address},
+    utils::{build_and_send_transaction, init_ata, init_mint, init_wallet, setup},
+};
+
+// Ceilings include the CPIs each instruction makes, raise them only for a deliberate cost
+pub const MAKE_COMPUTE_UNIT_CEILING: u64 = 60_000;
+pub const TAKE_COMPUTE_UNIT_CEILING: u64 = 40_000;
+
+#[test]
+fn compute_units_make_and_take() {
+    let (litesvm, _default_payer) = &mut setup();
+
+    let maker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+    let taker = init_wallet(litesvm, LAMPORTS_PER_SOL);
+    let mint_a = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+    let mint_b = init_mint(litesvm, TOKEN_PROGRAM_ID, MINT_DECIMALS, 1_000_000_000);
+    let treasury_ata_b = init_ata(litesvm, mint_b, TREASURY, 0);
+    let maker_ata_a = init_ata(litesvm, mint_a, maker.pubkey(), 1_000_000_000);
+    let taker_ata_b = init_ata(litesvm, mint_b, taker.pubkey(), 1_000_000_000);
+
+    // Existing ATAs keep ATA creation out of the Take measurement
+    let taker_ata_a = init_ata(litesvm, mint_a, taker.pubkey(), 0);
+    let maker_ata_b = init_ata(litesvm, mint_b, maker.pubkey(), 0);
+
+    let seed = 42u64;
+    let receive_amount: u64 = 100_000_000;
+    let give_amount: u64 = 500_000_000;
+    let escrow_pda = get_escrow_pda(&maker.pubkey(), seed);
+    let vault = get_vault_address(&escrow_pda, &mint_a, &TOKEN_PROGRAM_ID);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(maker_ata_a, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ],
+        data: [
+            vec![0u8],
+            vec![2u8],
+            seed.to_le_bytes().to_vec(),
+            receive_amount.to_le_bytes().to_vec(),
+            give_amount.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            0i64.to_le_bytes().to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+            [0u8; 32].to_vec(),
+        ]
+        .concat(),
+    };
+
+    let make_units = build_and_send_transaction(litesvm, &[&maker], &maker.pubkey(), &[ix])
+        .unwrap()
+        .compute_units_consumed;
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(escrow_pda, false),
+            AccountMeta::new_readonly(mint_a, false),
+            AccountMeta::new_readonly(mint_b, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(taker_ata_a, false),
+            AccountMeta::new(taker_ata_b, false),
+            AccountMeta::new(maker_ata_b, false),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(treasury_ata_b, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_config_pda(), false),
+        ],
+        data: [vec![1u8]].concat(),
+    };
+
+    let take_units = build_and_send_transaction(litesvm, &[&taker], &taker.pubkey(), &[ix])
+        .unwrap()
+        .compute_units_consumed;
+
+    println!("Make: {make_units} CU");
+    println!("Take: {take_units} CU");
+
+    assert!(
+        make_units <= MAKE_COMPUTE_UNIT_CEILING,
+        "Make used {make_units} CU, over the {MAKE_COMPUTE_UNIT_CEILING} CU ceiling"
+    );
+    assert!(
+        take_units <= TAKE_COMPUTE_UNIT_CEILING,
+        "Take used {take_units} CU, over the {TAKE_COMPUTE_UNIT_CEILING} CU ceiling"
+    );
+}
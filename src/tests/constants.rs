@@ -6,7 +6,21 @@ pub static TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey::new_from_array(pinocchio_toke
 pub static ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
     Pubkey::new_from_array(pinocchio_associated_token_account::ID);
 
+pub static BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    Pubkey::new_from_array(crate::BPF_LOADER_UPGRADEABLE_ID);
+
 pub static PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+// Example caller in programs/escrow-caller that opens escrows through the cpi module,
+// 33333333333333333333333333333333333333333333
+pub static CALLER_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0x1e, 0x3c, 0xd6, 0x28, 0x43, 0x80, 0x94, 0x0e, 0x08, 0x62, 0x4c, 0xb8, 0x33, 0x8b, 0x77, 0xdc,
+    0x33, 0x25, 0x75, 0xd1, 0x5f, 0xa3, 0x9a, 0x0f, 0x1d, 0xf1, 0x5e, 0xe0, 0x8f, 0xb8, 0x23, 0xee,
+]);
+pub static NATIVE_MINT: Pubkey = Pubkey::new_from_array(crate::NATIVE_MINT);
+pub static TREASURY: Pubkey = Pubkey::new_from_array(crate::TREASURY);
 pub const MINT_DECIMALS: u8 = 6;
 
-pub static ESCROW_SEED: &[u8] = b"escrow";
+pub static ESCROW_SEED: &[u8] = crate::ESCROW_SEED_PREFIX;
+pub static CONFIG_SEED: &[u8] = crate::CONFIG_SEED;
+pub static COMMITMENT_SEED: &[u8] = crate::COMMITMENT_SEED;
+pub static CALLER_AUTHORITY_SEED: &[u8] = b"authority";
@@ -0,0 +1,89 @@
+use solana_instruction::{Instruction, error::InstructionError};
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+
+use crate::{
+    Amend, Claim, CloseEscrow, CommitTake, ExtendExpiry, InitConfig, Make, MakeAndTake,
+    MakeDelegated, MigrateEscrow, QueryEscrow, Reap, Refund, RevealTake, SetFee, SetFeeRecipient,
+    SetMinDeposit, SetPaused, SetReceiveOptions, Split, Take, TakeMany, TakeNative, TakeTo, TopUp,
+    TransferMaker, UpdateReceive,
+    tests::{
+        constants::PROGRAM_ID,
+        utils::{build_and_send_transaction, setup},
+    },
+};
+
+#[test]
+fn dispatch_known_discriminators() {
+    let (litesvm, default_payer) = &mut setup();
+
+    for discriminator in [
+        Make::DISCRIMINATOR,
+        Take::DISCRIMINATOR,
+        Refund::DISCRIMINATOR,
+        TopUp::DISCRIMINATOR,
+        UpdateReceive::DISCRIMINATOR,
+        TakeMany::DISCRIMINATOR,
+        QueryEscrow::DISCRIMINATOR,
+        Amend::DISCRIMINATOR,
+        Claim::DISCRIMINATOR,
+        TransferMaker::DISCRIMINATOR,
+        TakeTo::DISCRIMINATOR,
+        Split::DISCRIMINATOR,
+        MakeDelegated::DISCRIMINATOR,
+        InitConfig::DISCRIMINATOR,
+        SetPaused::DISCRIMINATOR,
+        TakeNative::DISCRIMINATOR,
+        Reap::DISCRIMINATOR,
+        CloseEscrow::DISCRIMINATOR,
+        SetReceiveOptions::DISCRIMINATOR,
+        MigrateEscrow::DISCRIMINATOR,
+        CommitTake::DISCRIMINATOR,
+        RevealTake::DISCRIMINATOR,
+        ExtendExpiry::DISCRIMINATOR,
+        SetFee::DISCRIMINATOR,
+        SetFeeRecipient::DISCRIMINATOR,
+        MakeAndTake::DISCRIMINATOR,
+        SetMinDeposit::DISCRIMINATOR,
+    ] {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![],
+            data: vec![*discriminator],
+        };
+
+        let result =
+            build_and_send_transaction(litesvm, &[default_payer], &default_payer.pubkey(), &[ix]);
+
+        // Each instruction is reached and rejects the empty account list
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+        );
+
+        litesvm.expire_blockhash();
+    }
+}
+
+#[test]
+fn dispatch_unknown_discriminator() {
+    let (litesvm, default_payer) = &mut setup();
+
+    for data in [vec![], vec![27u8], vec![u8::MAX]] {
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![],
+            data,
+        };
+
+        let result =
+            build_and_send_transaction(litesvm, &[default_payer], &default_payer.pubkey(), &[ix]);
+
+        assert_eq!(
+            result.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+
+        litesvm.expire_blockhash();
+    }
+}
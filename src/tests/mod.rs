@@ -6,3 +6,12 @@ pub mod pda;
 
 #[cfg(test)]
 pub mod utils;
+
+#[cfg(test)]
+pub mod dispatch;
+
+#[cfg(test)]
+pub mod compute_units;
+
+#[cfg(test)]
+pub mod program_id;
@@ -1,6 +1,10 @@
 use solana_pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
-use crate::tests::constants::{ESCROW_SEED, PROGRAM_ID};
+use crate::tests::constants::{
+    BPF_LOADER_UPGRADEABLE_ID, CALLER_AUTHORITY_SEED, CALLER_PROGRAM_ID, COMMITMENT_SEED,
+    CONFIG_SEED, ESCROW_SEED, PROGRAM_ID,
+};
 
 pub fn get_escrow_pda(maker: &Pubkey, seed: u64) -> Pubkey {
     Pubkey::find_program_address(
@@ -9,3 +13,25 @@ pub fn get_escrow_pda(maker: &Pubkey, seed: u64) -> Pubkey {
     )
     .0
 }
+
+pub fn get_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], &PROGRAM_ID).0
+}
+
+pub fn get_program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID).0
+}
+
+pub fn get_commitment_pda(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COMMITMENT_SEED, escrow.as_ref()], &PROGRAM_ID).0
+}
+
+// The vault is the escrow's ATA for mint A
+pub fn get_vault_address(escrow: &Pubkey, mint_a: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(escrow, mint_a, token_program)
+}
+
+// The example caller program's PDA, which it signs for as the maker
+pub fn get_caller_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[CALLER_AUTHORITY_SEED], &CALLER_PROGRAM_ID).0
+}
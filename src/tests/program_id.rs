@@ -0,0 +1,16 @@
+use solana_pubkey::Pubkey;
+
+use crate::tests::constants::PROGRAM_ID;
+
+#[test]
+fn program_id_matches_cluster_feature() {
+    let expected = if cfg!(feature = "devnet") {
+        Pubkey::from_str_const("Gegn5GsVipTyfF8tkkvmz3fVTXW2DDmKPkK5brHewE3z")
+    } else if cfg!(feature = "mainnet") {
+        Pubkey::from_str_const("GA79r9t7DxFHcDCnXfA6a8PFoag5no2kJxgzGdriDJtJ")
+    } else {
+        Pubkey::from_str_const("22222222222222222222222222222222222222222222")
+    };
+
+    assert_eq!(PROGRAM_ID, expected);
+}
@@ -1,23 +1,66 @@
-use litesvm::{LiteSVM, types::TransactionResult};
+use litesvm::{
+    LiteSVM,
+    types::{FailedTransactionMetadata, SimulatedTransactionInfo, TransactionResult},
+};
+use pinocchio::program_error::ProgramError;
 use solana_account::Account;
-use solana_instruction::Instruction;
+use solana_instruction::{AccountMeta, Instruction, error::InstructionError};
 use solana_keypair::Keypair;
 use solana_message::Message;
 use solana_pubkey::Pubkey;
 use solana_signer::Signer;
 use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
 use spl_associated_token_account::{
-    get_associated_token_address,
-    solana_program::{
-        native_token::LAMPORTS_PER_SOL,
-        program_pack::{IsInitialized, Pack},
+    get_associated_token_address_with_program_id,
+    solana_program::{native_token::LAMPORTS_PER_SOL, program_pack::Pack},
+};
+use spl_token_2022::{
+    extension::{
+        BaseState, BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
+        StateWithExtensions, StateWithExtensionsMut,
+        default_account_state::DefaultAccountState,
+        permanent_delegate::PermanentDelegate,
+        transfer_fee::{TransferFee, TransferFeeConfig},
     },
+    state::{Account as TokenAccount, AccountState, Mint},
 };
-use spl_token_2022::state::{Account as TokenAccount, AccountState, Mint};
 
-use crate::tests::constants::PROGRAM_ID;
+use crate::tests::{
+    constants::{
+        BPF_LOADER_UPGRADEABLE_ID, NATIVE_MINT, PROGRAM_ID, SYSTEM_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+    pda::{get_config_pda, get_program_data_address},
+};
 
+// Initializes the config with the default payer as admin, since Make and Take require it
 pub fn setup() -> (LiteSVM, Keypair) {
+    let (mut litesvm, default_payer) = setup_without_config();
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(default_payer.pubkey(), true),
+            AccountMeta::new(get_config_pda(), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(get_program_data_address(), false),
+        ],
+        data: vec![13u8],
+    };
+
+    build_and_send_transaction(
+        &mut litesvm,
+        &[&default_payer],
+        &default_payer.pubkey(),
+        &[ix],
+    )
+    .unwrap();
+
+    (litesvm, default_payer)
+}
+
+pub fn setup_without_config() -> (LiteSVM, Keypair) {
     let mut litesvm = LiteSVM::new();
 
     litesvm
@@ -29,20 +72,77 @@ pub fn setup() -> (LiteSVM, Keypair) {
         .airdrop(&default_payer.pubkey(), LAMPORTS_PER_SOL * 100)
         .unwrap();
 
+    // The default payer deployed the program, so it is the one allowed to run InitConfig
+    set_upgrade_authority(&mut litesvm, Some(default_payer.pubkey()));
+
     (litesvm, default_payer)
 }
 
+// Writes the program's ProgramData account, which add_program_from_file doesn't create
+pub fn set_upgrade_authority(litesvm: &mut LiteSVM, authority: Option<Pubkey>) {
+    let mut data = [3u32.to_le_bytes().to_vec(), 0u64.to_le_bytes().to_vec()].concat();
+    match authority {
+        Some(authority) => data.extend([vec![1u8], authority.to_bytes().to_vec()].concat()),
+        None => data.push(0),
+    }
+
+    let lamports = litesvm.minimum_balance_for_rent_exemption(data.len());
+
+    litesvm
+        .set_account(
+            get_program_data_address(),
+            Account {
+                lamports,
+                data,
+                owner: BPF_LOADER_UPGRADEABLE_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
 fn pack_data<T: Pack>(state: T) -> Vec<u8> {
     let mut data = vec![0; T::LEN];
     T::pack(state, &mut data).unwrap();
     data
 }
 
-pub fn fetch_account<T: Pack + IsInitialized>(litesvm: &LiteSVM, pubkey: &Pubkey) -> T {
+fn pack_account_with_extensions(mint_data: &[u8], state: TokenAccount) -> Vec<u8> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data).unwrap();
+    let extension_types =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types().unwrap());
+
+    if extension_types.is_empty() {
+        return pack_data(state);
+    }
+
+    let space = ExtensionType::try_calculate_account_len::<TokenAccount>(&extension_types).unwrap();
+    let mut data = vec![0; space];
+    let mut account =
+        StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+
+    for extension_type in extension_types {
+        account
+            .init_account_extension_from_type(extension_type)
+            .unwrap();
+    }
+
+    account.base = state;
+    account.pack_base();
+    account.init_account_type().unwrap();
+
+    data
+}
+
+pub fn fetch_account<T: BaseState + Pack>(litesvm: &LiteSVM, pubkey: &Pubkey) -> T {
     let account = litesvm.get_account(pubkey).unwrap();
-    T::unpack(&mut account.data.as_ref()).unwrap()
+    StateWithExtensions::<T>::unpack(account.data.as_ref())
+        .unwrap()
+        .base
 }
 
+#[allow(clippy::result_large_err)]
 pub fn build_and_send_transaction(
     litesvm: &mut LiteSVM,
     signers: &[&Keypair],
@@ -57,6 +157,65 @@ pub fn build_and_send_transaction(
     litesvm.send_transaction(tx)
 }
 
+#[allow(clippy::result_large_err)]
+pub fn build_and_simulate_transaction(
+    litesvm: &LiteSVM,
+    signers: &[&Keypair],
+    payer: &Pubkey,
+    ixs: &[Instruction],
+) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+    let tx = Transaction::new(
+        signers,
+        Message::new(ixs, Some(payer)),
+        litesvm.latest_blockhash(),
+    );
+    litesvm.simulate_transaction(tx)
+}
+
+// Simulates the transaction and asserts it failed with the expected program error
+pub fn simulate_expect_error(
+    litesvm: &LiteSVM,
+    signers: &[&Keypair],
+    payer: &Pubkey,
+    ixs: &[Instruction],
+    expected: ProgramError,
+) {
+    let Err(result) = build_and_simulate_transaction(litesvm, signers, payer, ixs) else {
+        panic!("expected the transaction to fail with {expected:?}");
+    };
+    let TransactionError::InstructionError(_, err) = result.err else {
+        panic!("expected an instruction error, got {:?}", result.err);
+    };
+
+    assert_eq!(err, to_instruction_error(expected));
+}
+
+fn to_instruction_error(err: ProgramError) -> InstructionError {
+    match err {
+        ProgramError::Custom(code) => InstructionError::Custom(code),
+        ProgramError::InvalidArgument => InstructionError::InvalidArgument,
+        ProgramError::InvalidInstructionData => InstructionError::InvalidInstructionData,
+        ProgramError::InvalidAccountData => InstructionError::InvalidAccountData,
+        ProgramError::AccountDataTooSmall => InstructionError::AccountDataTooSmall,
+        ProgramError::InsufficientFunds => InstructionError::InsufficientFunds,
+        ProgramError::IncorrectProgramId => InstructionError::IncorrectProgramId,
+        ProgramError::MissingRequiredSignature => InstructionError::MissingRequiredSignature,
+        ProgramError::AccountAlreadyInitialized => InstructionError::AccountAlreadyInitialized,
+        ProgramError::UninitializedAccount => InstructionError::UninitializedAccount,
+        ProgramError::NotEnoughAccountKeys => InstructionError::NotEnoughAccountKeys,
+        ProgramError::AccountBorrowFailed => InstructionError::AccountBorrowFailed,
+        ProgramError::MaxSeedLengthExceeded => InstructionError::MaxSeedLengthExceeded,
+        ProgramError::InvalidSeeds => InstructionError::InvalidSeeds,
+        ProgramError::AccountNotRentExempt => InstructionError::AccountNotRentExempt,
+        ProgramError::IllegalOwner => InstructionError::IllegalOwner,
+        ProgramError::InvalidAccountOwner => InstructionError::InvalidAccountOwner,
+        ProgramError::ArithmeticOverflow => InstructionError::ArithmeticOverflow,
+        ProgramError::Immutable => InstructionError::Immutable,
+        ProgramError::IncorrectAuthority => InstructionError::IncorrectAuthority,
+        err => panic!("no instruction error mapped for {err:?}"),
+    }
+}
+
 pub fn init_wallet(litesvm: &mut LiteSVM, lamports: u64) -> Keypair {
     let wallet = Keypair::new();
     litesvm.airdrop(&wallet.pubkey(), lamports).unwrap();
@@ -93,9 +252,189 @@ pub fn init_mint(litesvm: &mut LiteSVM, owner: Pubkey, decimals: u8, supply: u64
     mint
 }
 
+pub fn init_native_mint(litesvm: &mut LiteSVM) -> Pubkey {
+    let mint_state = Mint {
+        mint_authority: None.into(),
+        supply: 0,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    };
+
+    let mint_data = pack_data(mint_state);
+    let lamports = litesvm.minimum_balance_for_rent_exemption(Mint::LEN);
+
+    litesvm
+        .set_account(
+            NATIVE_MINT,
+            Account {
+                lamports,
+                data: mint_data,
+                owner: TOKEN_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    NATIVE_MINT
+}
+
+pub fn init_mint_with_transfer_fee(
+    litesvm: &mut LiteSVM,
+    decimals: u8,
+    supply: u64,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Pubkey {
+    let mint = Keypair::new().pubkey();
+
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+            .unwrap();
+    let mut mint_data = vec![0; space];
+    let mut mint_state =
+        StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+    let transfer_fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: maximum_fee.into(),
+        transfer_fee_basis_points: transfer_fee_basis_points.into(),
+    };
+    let transfer_fee_config = mint_state
+        .init_extension::<TransferFeeConfig>(true)
+        .unwrap();
+    transfer_fee_config.older_transfer_fee = transfer_fee;
+    transfer_fee_config.newer_transfer_fee = transfer_fee;
+
+    mint_state.base = Mint {
+        mint_authority: None.into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    };
+    mint_state.pack_base();
+    mint_state.init_account_type().unwrap();
+
+    let lamports = litesvm.minimum_balance_for_rent_exemption(mint_data.len());
+
+    litesvm
+        .set_account(
+            mint,
+            Account {
+                lamports,
+                data: mint_data,
+                owner: TOKEN_2022_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    mint
+}
+
+pub fn init_mint_with_permanent_delegate(
+    litesvm: &mut LiteSVM,
+    decimals: u8,
+    supply: u64,
+    delegate: Pubkey,
+) -> Pubkey {
+    let mint = Keypair::new().pubkey();
+
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::PermanentDelegate])
+            .unwrap();
+    let mut mint_data = vec![0; space];
+    let mut mint_state =
+        StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+    mint_state
+        .init_extension::<PermanentDelegate>(true)
+        .unwrap()
+        .delegate = Some(delegate).try_into().unwrap();
+
+    mint_state.base = Mint {
+        mint_authority: None.into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    };
+    mint_state.pack_base();
+    mint_state.init_account_type().unwrap();
+
+    let lamports = litesvm.minimum_balance_for_rent_exemption(mint_data.len());
+
+    litesvm
+        .set_account(
+            mint,
+            Account {
+                lamports,
+                data: mint_data,
+                owner: TOKEN_2022_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    mint
+}
+
+pub fn init_mint_with_default_account_state(
+    litesvm: &mut LiteSVM,
+    decimals: u8,
+    supply: u64,
+    state: AccountState,
+) -> Pubkey {
+    let mint = Keypair::new().pubkey();
+
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::DefaultAccountState])
+            .unwrap();
+    let mut mint_data = vec![0; space];
+    let mut mint_state =
+        StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+    mint_state
+        .init_extension::<DefaultAccountState>(true)
+        .unwrap()
+        .state = state as u8;
+
+    mint_state.base = Mint {
+        mint_authority: None.into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    };
+    mint_state.pack_base();
+    mint_state.init_account_type().unwrap();
+
+    let lamports = litesvm.minimum_balance_for_rent_exemption(mint_data.len());
+
+    litesvm
+        .set_account(
+            mint,
+            Account {
+                lamports,
+                data: mint_data,
+                owner: TOKEN_2022_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    mint
+}
+
 pub fn init_ata(litesvm: &mut LiteSVM, mint: Pubkey, owner: Pubkey, amount: u64) -> Pubkey {
-    let token_program = litesvm.get_account(&mint).unwrap().owner;
-    let ata = get_associated_token_address(&owner, &mint);
+    let mint_account = litesvm.get_account(&mint).unwrap();
+    let token_program = mint_account.owner;
+    let ata = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
 
     let ata_state = TokenAccount {
         mint,
@@ -108,8 +447,12 @@ pub fn init_ata(litesvm: &mut LiteSVM, mint: Pubkey, owner: Pubkey, amount: u64)
         close_authority: None.into(),
     };
 
-    let ata_data = pack_data(ata_state);
-    let lamports = litesvm.minimum_balance_for_rent_exemption(TokenAccount::LEN);
+    let ata_data = if token_program == TOKEN_2022_PROGRAM_ID {
+        pack_account_with_extensions(&mint_account.data, ata_state)
+    } else {
+        pack_data(ata_state)
+    };
+    let lamports = litesvm.minimum_balance_for_rent_exemption(ata_data.len());
 
     litesvm
         .set_account(
@@ -126,3 +469,14 @@ pub fn init_ata(litesvm: &mut LiteSVM, mint: Pubkey, owner: Pubkey, amount: u64)
 
     ata
 }
+
+// Freezes a token account in place, as its mint's freeze authority would
+pub fn freeze_token_account(litesvm: &mut LiteSVM, token_account: Pubkey) {
+    let mut account = litesvm.get_account(&token_account).unwrap();
+    let mut state = StateWithExtensionsMut::<TokenAccount>::unpack(&mut account.data).unwrap();
+
+    state.base.state = AccountState::Frozen;
+    state.pack_base();
+
+    litesvm.set_account(token_account, account).unwrap();
+}